@@ -1,3 +1,4 @@
+use crate::core::sink::{ChannelSink, MessageSink, OutgoingMessage};
 use lazy_static::lazy_static;
 use serenity::{
     framework::standard::{macros::command, Args, CommandError, CommandResult},
@@ -12,31 +13,34 @@ enum Unit {
 }
 
 enum VelocityType {
-    KILOMETERSPERHOUR,
-    MILESPERHOUR,
-    METERSPERSECOND,
-    FEETPERSECOND,
+    KilometersPerHour,
+    MilesPerHour,
+    MetersPerSecond,
+    FeetPerSecond,
 }
 
 enum DistanceType {
-    KILOMETER,
-    METER,
-    CENTIMETER,
-    MILLIMETER,
-    MILE,
-    FOOT,
-    YARD,
-    INCH,
+    Kilometer,
+    Meter,
+    Centimeter,
+    Millimeter,
+    Mile,
+    Foot,
+    Yard,
+    Inch,
 }
 
 enum TemperatureType {
-    CELSIUS,
-    KELVIN,
-    FAHRENHEIT,
+    Celsius,
+    Kelvin,
+    Fahrenheit,
 }
 
+// Converts a value from one unit into another
+type Conversion = fn(f64) -> f64;
+
 lazy_static! {
-    static ref VELOCITY_MATRIX: Vec<Vec<fn(f64) -> f64>> = vec![
+    static ref VELOCITY_MATRIX: Vec<Vec<Conversion>> = vec![
         //  kilometers per hour
         vec![
             |n| n,          // to kilometers per hour
@@ -67,7 +71,7 @@ lazy_static! {
         ],
     ];
 
-    static ref DISTANCE_MATRIX: Vec<Vec<fn(f64) -> f64>> = vec![
+    static ref DISTANCE_MATRIX: Vec<Vec<Conversion>> = vec![
         // kilometer
         vec![
             |n| n,             // to kilometer
@@ -158,7 +162,7 @@ lazy_static! {
         ],
     ];
 
-    static ref TEMP_MATRIX: Vec<Vec<fn(f64) -> f64>> = vec![
+    static ref TEMP_MATRIX: Vec<Vec<Conversion>> = vec![
         // celcius
         vec![
             |n| n,              // to celcius
@@ -185,7 +189,13 @@ lazy_static! {
 #[example("25km/h mph")]
 #[example("5mi mm")]
 #[example("27°C °F")]
-pub async fn convert(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+pub async fn convert(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let sink = ChannelSink::new(&ctx.http, msg.channel_id);
+
+    send_conversion(&sink, args).await
+}
+
+async fn send_conversion(sink: &dyn MessageSink, mut args: Args) -> CommandResult {
     if args.len() != 2 {
         return Err(
            CommandError::from("Invalid number of arguments. You need to pass <number><unit> and <desired unit>, e.g. $convert 25km/s ft/s"),
@@ -229,11 +239,11 @@ pub async fn convert(ctx: &Context, msg: &Message, mut args: Args) -> CommandRes
         _ => return Err(CommandError::from("Can't convert between unrelated units.")),
     };
 
-    let _ = msg
-        .channel_id
-        .send_message(&ctx.http, |m| {
-            m.content(format!("{} = {:.2}{}", source_arg, result, dest_unit_arg,))
-        })
+    let _ = sink
+        .send(OutgoingMessage::text(format!(
+            "{} = {:.2}{}",
+            source_arg, result, dest_unit_arg,
+        )))
         .await;
 
     Ok(())
@@ -241,33 +251,70 @@ pub async fn convert(ctx: &Context, msg: &Message, mut args: Args) -> CommandRes
 
 fn get_unit(unit_string: &str) -> Option<Unit> {
     match unit_string.to_lowercase().as_str() {
-        "kmh" | "km/h" => Some(Unit::Velocity(VelocityType::KILOMETERSPERHOUR)),
-        "ms" | "m/s" => Some(Unit::Velocity(VelocityType::METERSPERSECOND)),
-        "mph" | "m/h" => Some(Unit::Velocity(VelocityType::MILESPERHOUR)),
-        "fts" | "ft/s" => Some(Unit::Velocity(VelocityType::FEETPERSECOND)),
-
-        "km" => Some(Unit::Distance(DistanceType::KILOMETER)),
-        "m" => Some(Unit::Distance(DistanceType::METER)),
-        "cm" => Some(Unit::Distance(DistanceType::CENTIMETER)),
-        "mm" => Some(Unit::Distance(DistanceType::MILLIMETER)),
-        "mi" => Some(Unit::Distance(DistanceType::MILE)),
-        "ft" => Some(Unit::Distance(DistanceType::FOOT)),
-        "yd" => Some(Unit::Distance(DistanceType::YARD)),
-        "in" | "inch" | "inches" => Some(Unit::Distance(DistanceType::INCH)),
-
-        "c" | "°c" => Some(Unit::Temperature(TemperatureType::CELSIUS)),
-        "k" => Some(Unit::Temperature(TemperatureType::KELVIN)),
-        "f" | "°f" => Some(Unit::Temperature(TemperatureType::FAHRENHEIT)),
+        "kmh" | "km/h" => Some(Unit::Velocity(VelocityType::KilometersPerHour)),
+        "ms" | "m/s" => Some(Unit::Velocity(VelocityType::MetersPerSecond)),
+        "mph" | "m/h" => Some(Unit::Velocity(VelocityType::MilesPerHour)),
+        "fts" | "ft/s" => Some(Unit::Velocity(VelocityType::FeetPerSecond)),
+
+        "km" => Some(Unit::Distance(DistanceType::Kilometer)),
+        "m" => Some(Unit::Distance(DistanceType::Meter)),
+        "cm" => Some(Unit::Distance(DistanceType::Centimeter)),
+        "mm" => Some(Unit::Distance(DistanceType::Millimeter)),
+        "mi" => Some(Unit::Distance(DistanceType::Mile)),
+        "ft" => Some(Unit::Distance(DistanceType::Foot)),
+        "yd" => Some(Unit::Distance(DistanceType::Yard)),
+        "in" | "inch" | "inches" => Some(Unit::Distance(DistanceType::Inch)),
+
+        "c" | "°c" => Some(Unit::Temperature(TemperatureType::Celsius)),
+        "k" => Some(Unit::Temperature(TemperatureType::Kelvin)),
+        "f" | "°f" => Some(Unit::Temperature(TemperatureType::Fahrenheit)),
 
         _ => None,
     }
 }
 
 fn do_conversion(
-    matrix: &Vec<Vec<fn(f64) -> f64>>,
+    matrix: &[Vec<Conversion>],
     number: f64,
     matrix_source_index: usize,
     matrix_dest_index: usize,
 ) -> f64 {
     matrix[matrix_source_index][matrix_dest_index](number)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::RecordingSink;
+    use serenity::framework::standard::Delimiter;
+
+    fn args(text: &str) -> Args {
+        Args::new(text, &[Delimiter::Single(' ')])
+    }
+
+    #[tokio::test]
+    async fn sends_the_converted_value() {
+        let sink = RecordingSink::new();
+
+        send_conversion(&sink, args("100c f")).await.unwrap();
+        send_conversion(&sink, args("36km/h m/s")).await.unwrap();
+
+        let messages = sink.messages();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].content.as_deref(), Some("100c = 212.00f"));
+        assert_eq!(messages[1].content.as_deref(), Some("36km/h = 10.00m/s"));
+    }
+
+    #[tokio::test]
+    async fn rejects_unrelated_units() {
+        let sink = RecordingSink::new();
+
+        let result = send_conversion(&sink, args("5km mph")).await;
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Can't convert between unrelated units."
+        );
+        assert!(sink.messages().is_empty());
+    }
+}
@@ -41,7 +41,7 @@ pub async fn fetch(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
 
     while !end_reached {
         // Show typing status
-        let _ = msg.channel_id.broadcast_typing(&ctx.http);
+        let _ = msg.channel_id.broadcast_typing(&ctx.http).await;
 
         // Fetch REQUESTS_PER_ITER messages to process
         let _messages: Vec<Message> = msg
@@ -93,28 +93,14 @@ pub async fn fetch(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
                 break;
             }
 
-            // Gather all image attachments
-            let mut attachment_filter = message
-                .attachments
-                .iter()
-                .filter(|a| a.width.is_some())
-                .peekable();
-
-            // Gather all embedded images
-            let mut embed_filter = message
-                .embeds
-                .iter()
-                .filter_map(|e| e.image.clone())
-                .peekable();
-
             // If there's either attachments or embeds containing at least one image,
             // then add all of them to the link list and reset the "nothing found" counter to 0.
             // Otherwise increment the counter
-            if attachment_filter.peek().is_some() || embed_filter.peek().is_some() {
+            let images = image_urls(&message);
+            if !images.is_empty() {
                 message_nothing_found_counter = 0;
 
-                attachment_filter.for_each(|a| link_list.push(a.url.clone()));
-                embed_filter.for_each(|e| link_list.push(e.url.clone()));
+                link_list.extend(images);
             } else {
                 message_nothing_found_counter += 1;
 
@@ -183,3 +169,21 @@ pub async fn fetch(ctx: &Context, msg: &Message, mut args: Args) -> CommandResul
 
     Ok(())
 }
+
+/// URLs of all images in a message, both attached and embedded ones
+pub fn image_urls(message: &Message) -> Vec<String> {
+    // Attachments only have a width if they're images
+    let attachments = message
+        .attachments
+        .iter()
+        .filter(|a| a.width.is_some())
+        .map(|a| a.url.clone());
+
+    let embeds = message
+        .embeds
+        .iter()
+        .filter_map(|e| e.image.as_ref())
+        .map(|image| image.url.clone());
+
+    attachments.chain(embeds).collect()
+}
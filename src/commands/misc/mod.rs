@@ -2,17 +2,26 @@ use serenity::framework::standard::macros::group;
 
 mod convert;
 mod fetch;
+mod sauce;
 mod say;
 mod translate;
 mod weather;
 
 use self::convert::CONVERT_COMMAND;
 use self::fetch::FETCH_COMMAND;
+use self::sauce::SAUCE_COMMAND;
 use self::say::SAY_COMMAND;
 use self::say::YELL_COMMAND;
-use self::translate::TRANSLATE_COMMAND;
+use self::translate::{ROMANIZE_COMMAND, TRANSLATE_COMMAND};
 use self::weather::WEATHER_COMMAND;
 
+pub use self::translate::backend::{create_translator, TranslatorContainer};
+pub use self::translate::{
+    relay_bridged_message, translate_flag_reaction, BridgesContainer, FlagTranslationsContainer,
+};
+pub use self::weather::provider::{create_provider, WeatherProviderContainer};
+pub use self::weather::subscription::{run_subscription_scheduler, WeatherSubscriptionsContainer};
+
 #[group]
-#[commands(convert, say, yell, weather, translate, fetch)]
+#[commands(convert, say, yell, weather, translate, romanize, fetch, sauce)]
 struct Misc;
@@ -0,0 +1,333 @@
+use super::fetch::image_urls;
+use crate::core::{
+    config::{get_config, SauceConfig},
+    consts::MAIN_COLOR,
+    http::{get_http_client, HttpClient, Service},
+};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_aux::field_attributes::deserialize_number_from_string;
+use serenity::{
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    model::channel::Message,
+    prelude::Context,
+};
+// How many matches are shown at most
+const MAX_RESULTS: usize = 3;
+
+// Matches less similar than this, in percent, are most likely different images
+const MIN_SIMILARITY: f64 = 55.0;
+
+#[command]
+#[aliases("source")]
+#[description(
+    "Finds the source of an image on SauceNAO. \
+    Attach the image, reply to a message with one, or give its url."
+)]
+#[usage("[image url]")]
+#[example("https://example.com/image.png")]
+pub async fn sauce(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let image_url = match find_image_url(msg, args.rest().trim()) {
+        Some(image_url) => image_url,
+        None => {
+            return Err(CommandError::from(
+                "You need to attach an image, reply to a message with one, or give its url",
+            ))
+        }
+    };
+
+    let _ = msg.channel_id.broadcast_typing(&ctx.http).await;
+
+    let client = get_http_client(ctx).await;
+    let config = get_config(ctx).await;
+    let results = best_matches(search(&client, &config.sauce, &image_url).await?);
+
+    if results.is_empty() {
+        let _ = msg
+            .channel_id
+            .send_message(&ctx.http, |m| {
+                m.content("I couldn't find the source of that image")
+            })
+            .await;
+
+        return Ok(());
+    }
+
+    // The match could be explicit, so its preview is only shown where that's fine
+    let is_nsfw = msg.channel_id.to_channel(ctx).await?.is_nsfw();
+
+    let _ = msg
+        .channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.colour(MAIN_COLOR).title("Sauce");
+
+                if is_nsfw {
+                    e.thumbnail(&results[0].header.thumbnail);
+                }
+
+                for result in &results {
+                    e.field(
+                        format!(
+                            "{:.1}% match on {}",
+                            result.header.similarity, result.header.index_name
+                        ),
+                        result.describe(),
+                        false,
+                    );
+                }
+
+                e
+            })
+        })
+        .await;
+
+    Ok(())
+}
+
+// The image is taken from the command's message first, then from the replied to message
+fn find_image_url(msg: &Message, url_arg: &str) -> Option<String> {
+    if url_arg.starts_with("http://") || url_arg.starts_with("https://") {
+        return Some(url_arg.to_string());
+    }
+
+    image_urls(msg).into_iter().next().or_else(|| {
+        msg.referenced_message
+            .as_ref()
+            .and_then(|referenced| image_urls(referenced).into_iter().next())
+    })
+}
+
+async fn search(
+    client: &HttpClient,
+    config: &SauceConfig,
+    image_url: &str,
+) -> Result<Vec<SauceResult>, CommandError> {
+    let mut params = vec![
+        // JSON output
+        ("output_type", "2".to_string()),
+        ("numres", (MAX_RESULTS * 2).to_string()),
+        ("url", image_url.to_string()),
+    ];
+    if let Some(api_key) = &config.api_key {
+        params.push(("api_key", api_key.clone()));
+    }
+
+    let resp = client
+        .send(Service::Sauce, client.get(&config.url).query(&params))
+        .await?;
+
+    match resp.status() {
+        StatusCode::OK => {}
+        StatusCode::TOO_MANY_REQUESTS => {
+            return Err(CommandError::from(
+                "Too many source searches for now, try again in a bit",
+            ))
+        }
+        s => {
+            return Err(CommandError::from(format!(
+                "Unexpected response status: {:?}",
+                s
+            )))
+        }
+    }
+
+    let response: SauceResponse = resp.json().await?;
+
+    response.into_results()
+}
+
+// Leaves out matches that are most likely different images
+fn best_matches(results: Vec<SauceResult>) -> Vec<SauceResult> {
+    results
+        .into_iter()
+        .filter(|result| result.header.similarity >= MIN_SIMILARITY)
+        .take(MAX_RESULTS)
+        .collect()
+}
+
+#[derive(Deserialize, Debug)]
+struct SauceResponse {
+    header: ResponseHeader,
+    // Missing if the search failed, e.g. because the image couldn't be downloaded
+    #[serde(default)]
+    results: Vec<SauceResult>,
+}
+
+// SauceNAO reports failed searches with a status other than 0, even though the request succeeded.
+// Negative ones are problems with the search itself, positive ones on SauceNAO's side.
+#[derive(Deserialize, Debug)]
+struct ResponseHeader {
+    status: i32,
+    message: Option<String>,
+}
+
+impl SauceResponse {
+    fn into_results(self) -> Result<Vec<SauceResult>, CommandError> {
+        // Some indexes failing still leaves the results of the others
+        if self.header.status == 0 || !self.results.is_empty() {
+            return Ok(self.results);
+        }
+
+        let message = self
+            .header
+            .message
+            .as_deref()
+            .map(strip_tags)
+            .filter(|message| !message.is_empty());
+
+        Err(CommandError::from(
+            match (self.header.status < 0, message) {
+                (true, Some(message)) => {
+                    format!("SauceNAO couldn't search for that image: {}", message)
+                }
+                (true, None) => "SauceNAO couldn't search for that image".to_string(),
+                (false, _) => "SauceNAO isn't working right now, try again later".to_string(),
+            },
+        ))
+    }
+}
+
+// SauceNAO's messages contain some HTML formatting
+fn strip_tags(text: &str) -> String {
+    let mut stripped = String::new();
+    let mut in_tag = false;
+
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => stripped.push(c),
+            _ => {}
+        }
+    }
+
+    stripped.trim().to_string()
+}
+
+#[derive(Deserialize, Debug)]
+struct SauceResult {
+    header: SauceHeader,
+    data: SauceData,
+}
+
+#[derive(Deserialize, Debug)]
+struct SauceHeader {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    similarity: f64,
+    thumbnail: String,
+    index_name: String,
+}
+
+// Which fields are present depends on the site the match is from
+#[derive(Deserialize, Debug)]
+struct SauceData {
+    #[serde(default)]
+    ext_urls: Vec<String>,
+    title: Option<String>,
+    source: Option<String>,
+    member_name: Option<String>,
+    author_name: Option<String>,
+    creator: Option<Creator>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum Creator {
+    Single(String),
+    Several(Vec<String>),
+}
+
+impl SauceResult {
+    fn describe(&self) -> String {
+        let data = &self.data;
+
+        let artist = data
+            .member_name
+            .clone()
+            .or_else(|| data.author_name.clone())
+            .or_else(|| match &data.creator {
+                Some(Creator::Single(creator)) => Some(creator.clone()),
+                Some(Creator::Several(creators)) if !creators.is_empty() => {
+                    Some(creators.join(", "))
+                }
+                _ => None,
+            });
+
+        let mut lines = Vec::new();
+        if let Some(title) = data.title.as_ref().filter(|title| !title.is_empty()) {
+            lines.push(format!("**{}**", title));
+        }
+        if let Some(artist) = artist.filter(|artist| !artist.is_empty()) {
+            lines.push(format!("by {}", artist));
+        }
+
+        lines.extend(data.ext_urls.iter().cloned());
+
+        // The source is usually a link, but can also be just the name of a work
+        if let Some(source) = data.source.as_ref().filter(|source| !source.is_empty()) {
+            match source.starts_with("http") {
+                true => lines.push(source.clone()),
+                false => lines.push(format!("from {}", source)),
+            }
+        }
+
+        match lines.is_empty() {
+            true => "No details available".to_string(),
+            false => lines.join("\n"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::fixture;
+
+    fn results(path: &str) -> Result<Vec<SauceResult>, CommandError> {
+        let response: SauceResponse = serde_json::from_str(&fixture(path)).unwrap();
+
+        response.into_results()
+    }
+
+    #[test]
+    fn shows_only_similar_matches() {
+        let matches = best_matches(results("sauce/search.json").unwrap());
+
+        let similarities: Vec<f64> = matches.iter().map(|m| m.header.similarity).collect();
+        assert_eq!(similarities, vec![96.21, 91.40, 88.02]);
+    }
+
+    #[test]
+    fn describes_the_matches() {
+        let descriptions: Vec<String> = results("sauce/search.json")
+            .unwrap()
+            .iter()
+            .map(SauceResult::describe)
+            .collect();
+
+        assert_eq!(
+            descriptions,
+            vec![
+                "**夏の日**\nby someartist\n\
+                https://www.pixiv.net/member_illust.php?mode=medium&illust_id=81234567",
+                "by someartist\nhttps://danbooru.donmai.us/post/show/4012345\n\
+                https://i.pximg.net/img-original/img/2020/05/11/00/00/00/81234567_p0.png",
+                "https://anidb.net/anime/12345\nfrom Some Show",
+                "https://mangadex.org/chapter/1\nfrom Some Manga",
+                "by someone, someone else\nhttps://e621.net/post/show/2",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_failed_searches() {
+        let why = results("sauce/invalid_image.json").unwrap_err();
+
+        assert_eq!(
+            why.to_string(),
+            "SauceNAO couldn't search for that image: The file you supplied does not appear \
+            to be an image...Please make sure the file is a jpg, png, gif, bmp, or webp."
+        );
+    }
+}
@@ -12,9 +12,9 @@ use serenity::{
 pub async fn say(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     let _ = msg
         .channel_id
-        .send_message(&ctx.http, |m| m.content(&args.rest()))
+        .send_message(&ctx.http, |m| m.content(args.rest()))
         .await;
-    let _ = msg.delete(&ctx.http);
+    let _ = msg.delete(&ctx.http).await;
 
     Ok(())
 }
@@ -31,7 +31,7 @@ pub async fn yell(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
             m.content(format!("**{}**", &args.rest().to_uppercase()))
         })
         .await;
-    let _ = msg.delete(&ctx.http);
+    let _ = msg.delete(&ctx.http).await;
 
     Ok(())
 }
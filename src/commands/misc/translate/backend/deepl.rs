@@ -0,0 +1,180 @@
+use super::error::{parse_response, TranslateError};
+use super::{Translation, Translator, TranslatorResult};
+use crate::core::http::{HttpClient, Service};
+use reqwest::header::AUTHORIZATION;
+use serde::Deserialize;
+use serenity::async_trait;
+
+// DeepL accepts up to 128 KiB per request, but shorter texts keep the response time reasonable
+const MAX_TEXT_LENGTH: usize = 5000;
+
+// Our language codes and their DeepL counterparts. DeepL wants a regional variant
+// for some target languages, which is given separately.
+static LANGUAGES: &[(&str, &str, Option<&str>)] = &[
+    ("ar", "AR", None),
+    ("bg", "BG", None),
+    ("cs", "CS", None),
+    ("da", "DA", None),
+    ("de", "DE", None),
+    ("el", "EL", None),
+    ("en", "EN", Some("EN-US")),
+    ("es", "ES", None),
+    ("et", "ET", None),
+    ("fi", "FI", None),
+    ("fr", "FR", None),
+    ("hu", "HU", None),
+    ("id", "ID", None),
+    ("it", "IT", None),
+    ("ja", "JA", None),
+    ("ko", "KO", None),
+    ("lt", "LT", None),
+    ("lv", "LV", None),
+    ("nl", "NL", None),
+    ("no", "NB", None),
+    ("pl", "PL", None),
+    ("pt", "PT", Some("PT-PT")),
+    ("ro", "RO", None),
+    ("ru", "RU", None),
+    ("sk", "SK", None),
+    ("sl", "SL", None),
+    ("sv", "SV", None),
+    ("tr", "TR", None),
+    ("uk", "UK", None),
+    ("zh-CN", "ZH", Some("ZH-HANS")),
+];
+
+pub struct DeepLTranslator {
+    client: HttpClient,
+    api_key: String,
+}
+
+impl DeepLTranslator {
+    pub fn new(client: HttpClient, api_key: String) -> Self {
+        DeepLTranslator { client, api_key }
+    }
+
+    // Keys of the free plan end with ":fx" and have to use a separate endpoint
+    fn endpoint(&self) -> &'static str {
+        match self.api_key.ends_with(":fx") {
+            true => "https://api-free.deepl.com/v2/translate",
+            false => "https://api.deepl.com/v2/translate",
+        }
+    }
+}
+
+#[async_trait]
+impl Translator for DeepLTranslator {
+    fn name(&self) -> &'static str {
+        "DeepL"
+    }
+
+    fn max_text_length(&self) -> usize {
+        MAX_TEXT_LENGTH
+    }
+
+    async fn translate(
+        &self,
+        source_lang: Option<&str>,
+        target_lang: &str,
+        text: &str,
+    ) -> TranslatorResult<Translation> {
+        let target = match find_language(target_lang) {
+            Some((_, code, variant)) => variant.unwrap_or(code),
+            None => return Err(TranslateError::UnsupportedLanguage(target_lang.to_string())),
+        };
+
+        let mut form = vec![("text", text), ("target_lang", target)];
+
+        if let Some(source_lang) = source_lang {
+            match find_language(source_lang) {
+                Some((_, code, _)) => form.push(("source_lang", code)),
+                None => return Err(TranslateError::UnsupportedLanguage(source_lang.to_string())),
+            }
+        }
+
+        let request = self
+            .client
+            .post(self.endpoint())
+            .header(AUTHORIZATION, format!("DeepL-Auth-Key {}", self.api_key))
+            .form(&form);
+        let response = self.client.send(Service::Translate, request).await?;
+
+        let response: TranslateResponse = parse_response(response).await?;
+
+        Ok(response.into_translation(self.name()))
+    }
+}
+
+fn find_language(
+    code: &str,
+) -> Option<&'static (&'static str, &'static str, Option<&'static str>)> {
+    LANGUAGES.iter().find(|(ours, _, _)| *ours == code)
+}
+
+fn from_deepl_code(code: &str) -> String {
+    LANGUAGES
+        .iter()
+        .find(|(_, deepl, _)| deepl.eq_ignore_ascii_case(code))
+        .map(|(ours, _, _)| ours.to_string())
+        .unwrap_or_else(|| code.to_ascii_lowercase())
+}
+
+#[derive(Deserialize, Debug)]
+struct TranslateResponse {
+    translations: Vec<TranslationResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+struct TranslationResponse {
+    detected_source_language: String,
+    text: String,
+}
+
+impl TranslateResponse {
+    fn into_translation(self, translator: &'static str) -> Translation {
+        Translation {
+            source_lang: self
+                .translations
+                .first()
+                .map(|t| from_deepl_code(&t.detected_source_language)),
+            source_romanization: None,
+            target_romanization: None,
+            text: self.translations.into_iter().map(|t| t.text).collect(),
+            translator,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::error::parse_body;
+    use super::*;
+    use crate::core::testing::fixture;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn parses_the_translation() {
+        let response: TranslateResponse =
+            parse_body(StatusCode::OK, &fixture("translate/deepl.json")).unwrap();
+        let translation = response.into_translation("DeepL");
+
+        assert_eq!(translation.text, "Good morning, how are you?");
+        assert_eq!(translation.source_lang.as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn reports_an_exhausted_quota() {
+        let result: TranslatorResult<TranslateResponse> =
+            parse_body(StatusCode::from_u16(456).unwrap(), "");
+
+        assert!(matches!(result, Err(TranslateError::QuotaExceeded)));
+    }
+
+    #[test]
+    fn maps_language_codes() {
+        assert_eq!(from_deepl_code("ZH"), "zh-CN");
+        assert_eq!(from_deepl_code("NB"), "no");
+        assert_eq!(from_deepl_code("XX"), "xx");
+        assert_eq!(find_language("en").unwrap().2, Some("EN-US"));
+    }
+}
@@ -0,0 +1,111 @@
+use reqwest::{Response, StatusCode};
+use serde::de::DeserializeOwned;
+use std::{error::Error, fmt};
+
+#[derive(Debug)]
+pub enum TranslateError {
+    // The backend can't translate from or into this language
+    UnsupportedLanguage(String),
+    InvalidApiKey,
+    QuotaExceeded,
+    RateLimited,
+    Unavailable(StatusCode),
+    // An error message sent by the backend itself
+    Rejected(String),
+    InvalidResponse(serde_json::Error),
+    Request(reqwest::Error),
+}
+
+impl TranslateError {
+    /// Whether the error was caused by the user's input or a temporary condition,
+    /// rather than something the bot owner needs to look into
+    pub fn is_user_facing(&self) -> bool {
+        matches!(
+            self,
+            TranslateError::UnsupportedLanguage(_) | TranslateError::RateLimited
+        )
+    }
+}
+
+impl fmt::Display for TranslateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranslateError::UnsupportedLanguage(language) => {
+                write!(f, "I can't translate from or into '{}'.", language)
+            }
+            TranslateError::InvalidApiKey => {
+                write!(f, "The translation api key is invalid.")
+            }
+            TranslateError::QuotaExceeded => {
+                write!(f, "The translation quota for this month is used up.")
+            }
+            TranslateError::RateLimited => write!(
+                f,
+                "I've translated too much at once, please try again in a minute."
+            ),
+            TranslateError::Unavailable(status) => write!(
+                f,
+                "The translation service is unavailable right now ({}).",
+                status
+            ),
+            TranslateError::Rejected(message) => {
+                write!(
+                    f,
+                    "The translation service refused the request: {}",
+                    message
+                )
+            }
+            TranslateError::InvalidResponse(why) => write!(
+                f,
+                "The translation service sent an unexpected response: {}",
+                why
+            ),
+            TranslateError::Request(why) => {
+                write!(f, "Couldn't reach the translation service: {}", why)
+            }
+        }
+    }
+}
+
+impl Error for TranslateError {}
+
+impl From<reqwest::Error> for TranslateError {
+    fn from(why: reqwest::Error) -> Self {
+        TranslateError::Request(why)
+    }
+}
+
+/// Checks the status of a backend response and parses its body
+pub async fn parse_response<T: DeserializeOwned>(response: Response) -> Result<T, TranslateError> {
+    let status = response.status();
+    let body = response.text().await?;
+
+    parse_body(status, &body)
+}
+
+/// Maps a response's status and body to either the parsed data or the matching error
+pub fn parse_body<T: DeserializeOwned>(
+    status: StatusCode,
+    body: &str,
+) -> Result<T, TranslateError> {
+    match status.as_u16() {
+        401 | 403 => Err(TranslateError::InvalidApiKey),
+        // DeepL's non-standard status for an exhausted character quota
+        456 => Err(TranslateError::QuotaExceeded),
+        429 => Err(TranslateError::RateLimited),
+        400 => Err(TranslateError::Rejected(error_message(body))),
+        _ if !status.is_success() => Err(TranslateError::Unavailable(status)),
+        _ => serde_json::from_str(body).map_err(TranslateError::InvalidResponse),
+    }
+}
+
+// Backends usually explain rejected requests in an "error" or "message" field
+fn error_message(body: &str) -> String {
+    let json: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+
+    json.get("error")
+        .or_else(|| json.get("message"))
+        .and_then(|message| message.as_str())
+        .unwrap_or("unknown error")
+        .to_string()
+}
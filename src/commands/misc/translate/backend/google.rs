@@ -0,0 +1,242 @@
+use super::error::parse_response;
+use super::{Translation, Translator, TranslatorResult};
+use crate::core::http::{HttpClient, Service};
+use serde::de::{self, Deserialize, Deserializer, IgnoredAny, SeqAccess, Visitor};
+use serde_json::Value;
+use serenity::async_trait;
+use std::fmt;
+
+// The text is sent as part of the url, where percent encoding can triple its length
+const MAX_TEXT_LENGTH: usize = 1500;
+
+/// The free endpoint used by Google's browser extensions. It doesn't need an api key,
+/// but isn't officially documented either, so its responses are parsed defensively.
+pub struct GoogleTranslator {
+    client: HttpClient,
+}
+
+impl GoogleTranslator {
+    pub fn new(client: HttpClient) -> Self {
+        GoogleTranslator { client }
+    }
+}
+
+#[async_trait]
+impl Translator for GoogleTranslator {
+    fn name(&self) -> &'static str {
+        "Google Translate"
+    }
+
+    fn max_text_length(&self) -> usize {
+        MAX_TEXT_LENGTH
+    }
+
+    async fn translate(
+        &self,
+        source_lang: Option<&str>,
+        target_lang: &str,
+        text: &str,
+    ) -> TranslatorResult<Translation> {
+        let request = self
+            .client
+            .get("https://translate.googleapis.com/translate_a/single")
+            .query(&[
+                ("client", "gtx"),
+                ("sl", source_lang.unwrap_or("auto")),
+                ("tl", target_lang),
+                // Translated sentences, followed by the romanized readings
+                ("dt", "t"),
+                ("dt", "rm"),
+                ("q", text),
+            ]);
+        let response = self.client.send(Service::Translate, request).await?;
+
+        let response: TranslateResponse = parse_response(response).await?;
+
+        Ok(response.into_translation(self.name()))
+    }
+
+    async fn romanize(
+        &self,
+        source_lang: Option<&str>,
+        text: &str,
+    ) -> TranslatorResult<Option<String>> {
+        // The reading of the source text is part of every translation, regardless of the target
+        let target_lang = match source_lang {
+            Some("en") => "de",
+            _ => "en",
+        };
+
+        let translation = self.translate(source_lang, target_lang, text).await?;
+
+        Ok(translation.source_romanization)
+    }
+}
+
+// The response is a nested array, e.g. [[["Hallo", "Hello", ...], ...], null, "en", ...],
+// of which only the translated sentences and the detected language are of interest
+struct TranslateResponse {
+    sentences: Vec<Sentence>,
+    source_lang: Option<String>,
+}
+
+// Either a translated sentence, followed by the original and some metadata, e.g. ["Hallo", "Hello", ...],
+// or the romanized readings of the whole translation and original, e.g. [null, null, "Kon'nichiwa", null]
+enum Sentence {
+    Translated(String),
+    Romanization {
+        target: Option<String>,
+        source: Option<String>,
+    },
+}
+
+impl TranslateResponse {
+    fn into_translation(self, translator: &'static str) -> Translation {
+        let mut translation = Translation {
+            text: String::new(),
+            source_lang: self.source_lang,
+            source_romanization: None,
+            target_romanization: None,
+            translator,
+        };
+
+        for sentence in self.sentences {
+            match sentence {
+                Sentence::Translated(text) => translation.text.push_str(&text),
+                Sentence::Romanization { target, source } => {
+                    translation.target_romanization =
+                        target.or(translation.target_romanization.take());
+                    translation.source_romanization =
+                        source.or(translation.source_romanization.take());
+                }
+            }
+        }
+
+        translation
+    }
+}
+
+impl<'de> Deserialize<'de> for TranslateResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ResponseVisitor;
+
+        impl<'de> Visitor<'de> for ResponseVisitor {
+            type Value = TranslateResponse;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a translation response array")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let sentences: Vec<Sentence> = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let _: Option<IgnoredAny> = seq.next_element()?;
+                let source_lang: Option<String> = seq.next_element()?.flatten();
+
+                while seq.next_element::<IgnoredAny>()?.is_some() {}
+
+                Ok(TranslateResponse {
+                    sentences,
+                    source_lang,
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(ResponseVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Sentence {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SentenceVisitor;
+
+        impl<'de> Visitor<'de> for SentenceVisitor {
+            type Value = Sentence;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a translated sentence array")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let translation: Option<String> = seq.next_element()?.flatten();
+                let _original: Option<IgnoredAny> = seq.next_element()?;
+                // Only strings are of interest here, other sentences have numbers or nothing at all there
+                let target: Option<Value> = seq.next_element()?;
+                let source: Option<Value> = seq.next_element()?;
+
+                while seq.next_element::<IgnoredAny>()?.is_some() {}
+
+                Ok(match translation {
+                    Some(translation) => Sentence::Translated(translation),
+                    None => Sentence::Romanization {
+                        target: target.and_then(into_string),
+                        source: source.and_then(into_string),
+                    },
+                })
+            }
+        }
+
+        deserializer.deserialize_seq(SentenceVisitor)
+    }
+}
+
+fn into_string(value: Value) -> Option<String> {
+    match value {
+        Value::String(string) => Some(string),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::error::{parse_body, TranslateError};
+    use super::*;
+    use crate::core::testing::fixture;
+    use reqwest::StatusCode;
+
+    fn parse(body: &str) -> TranslatorResult<Translation> {
+        let response: TranslateResponse = parse_body(StatusCode::OK, body)?;
+
+        Ok(response.into_translation("Google Translate"))
+    }
+
+    #[test]
+    fn joins_the_translated_sentences() {
+        let translation = parse(&fixture("translate/google.json")).unwrap();
+
+        assert_eq!(
+            translation.text,
+            "Good morning, how are you? I hope you slept well."
+        );
+        assert_eq!(translation.source_lang.as_deref(), Some("de"));
+        assert_eq!(translation.source_romanization, None);
+        assert_eq!(translation.target_romanization, None);
+    }
+
+    #[test]
+    fn reads_the_romanization_row() {
+        let translation = parse(&fixture("translate/google_romanized.json")).unwrap();
+
+        assert_eq!(translation.text, "Hello, how are you?");
+        assert_eq!(translation.source_lang.as_deref(), Some("ja"));
+        assert_eq!(
+            translation.source_romanization.as_deref(),
+            Some("Kon'nichiwa, genkidesuka?")
+        );
+        assert_eq!(translation.target_romanization, None);
+    }
+
+    #[test]
+    fn rejects_malformed_responses() {
+        let truncated = &fixture("translate/google.json")[..60];
+
+        for body in &[truncated, "", "[]", "{\"error\": \"quota\"}", "[[[1, 2]]]"] {
+            assert!(
+                matches!(parse(body), Err(TranslateError::InvalidResponse(_))),
+                "{:?} was parsed",
+                body
+            );
+        }
+    }
+}
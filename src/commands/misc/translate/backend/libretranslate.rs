@@ -0,0 +1,116 @@
+use super::error::parse_response;
+use super::{Translation, Translator, TranslatorResult};
+use crate::core::http::{HttpClient, Service};
+use serde::{Deserialize, Serialize};
+use serenity::async_trait;
+
+// LibreTranslate instances limit the text length themselves, 5000 being a common setting
+const MAX_TEXT_LENGTH: usize = 5000;
+
+// Language codes that differ between Google and LibreTranslate
+static CODE_MAPPINGS: &[(&str, &str)] = &[
+    ("zh-CN", "zh"),
+    ("zh-TW", "zt"),
+    ("iw", "he"),
+    ("jw", "jv"),
+    ("tl", "fil"),
+];
+
+/// A self-hosted LibreTranslate instance
+pub struct LibreTranslateTranslator {
+    client: HttpClient,
+    url: String,
+    api_key: Option<String>,
+}
+
+impl LibreTranslateTranslator {
+    pub fn new(client: HttpClient, url: String, api_key: Option<String>) -> Self {
+        LibreTranslateTranslator {
+            client,
+            url: url.trim_end_matches('/').to_string(),
+            api_key,
+        }
+    }
+}
+
+#[async_trait]
+impl Translator for LibreTranslateTranslator {
+    fn name(&self) -> &'static str {
+        "LibreTranslate"
+    }
+
+    fn max_text_length(&self) -> usize {
+        MAX_TEXT_LENGTH
+    }
+
+    async fn translate(
+        &self,
+        source_lang: Option<&str>,
+        target_lang: &str,
+        text: &str,
+    ) -> TranslatorResult<Translation> {
+        let request = TranslateRequest {
+            q: text,
+            source: source_lang.map_or("auto", to_libre_code),
+            target: to_libre_code(target_lang),
+            format: "text",
+            api_key: self.api_key.as_deref(),
+        };
+
+        let request = self
+            .client
+            .post(format!("{}/translate", self.url))
+            .json(&request);
+        let response = self.client.send(Service::Translate, request).await?;
+
+        let response: TranslateResponse = parse_response(response).await?;
+
+        Ok(Translation {
+            text: response.translated_text,
+            source_lang: response
+                .detected_language
+                .map(|detected| from_libre_code(&detected.language)),
+            source_romanization: None,
+            target_romanization: None,
+            translator: self.name(),
+        })
+    }
+}
+
+fn to_libre_code(code: &str) -> &str {
+    CODE_MAPPINGS
+        .iter()
+        .find(|(ours, _)| *ours == code)
+        .map_or(code, |(_, libre)| *libre)
+}
+
+fn from_libre_code(code: &str) -> String {
+    CODE_MAPPINGS
+        .iter()
+        .find(|(_, libre)| *libre == code)
+        .map_or(code, |(ours, _)| *ours)
+        .to_string()
+}
+
+#[derive(Serialize, Debug)]
+struct TranslateRequest<'a> {
+    q: &'a str,
+    source: &'a str,
+    target: &'a str,
+    format: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<&'a str>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct TranslateResponse {
+    translated_text: String,
+    // Only sent by newer versions, and only when the source language was auto detected
+    detected_language: Option<DetectedLanguage>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DetectedLanguage {
+    language: String,
+}
@@ -0,0 +1,173 @@
+mod deepl;
+mod error;
+mod google;
+mod libretranslate;
+
+pub use self::error::TranslateError;
+
+use self::deepl::DeepLTranslator;
+use self::google::GoogleTranslator;
+use self::libretranslate::LibreTranslateTranslator;
+use crate::core::{config::TranslateConfig, http::HttpClient};
+use log::{info, warn};
+use serenity::{async_trait, prelude::*};
+use std::sync::Arc;
+
+pub type TranslatorResult<T> = Result<T, TranslateError>;
+
+/// A translation service. Language codes are the ones of the language registry,
+/// each backend maps them to its own codes where they differ.
+#[async_trait]
+pub trait Translator: Send + Sync {
+    /// Name of the backend, shown in the embed footers
+    fn name(&self) -> &'static str;
+
+    /// Maximum length of the text per request, in bytes. Longer texts are split up beforehand.
+    fn max_text_length(&self) -> usize;
+
+    /// Translates a text, detecting its language if no source language is given
+    async fn translate(
+        &self,
+        source_lang: Option<&str>,
+        target_lang: &str,
+        text: &str,
+    ) -> TranslatorResult<Translation>;
+
+    /// Romanized reading of a text written in a non-latin script, e.g. romaji for Japanese.
+    /// Returns None if the backend doesn't support it.
+    async fn romanize(
+        &self,
+        _source_lang: Option<&str>,
+        _text: &str,
+    ) -> TranslatorResult<Option<String>> {
+        Ok(None)
+    }
+}
+
+pub struct TranslatorContainer;
+impl TypeMapKey for TranslatorContainer {
+    type Value = Arc<dyn Translator>;
+}
+
+#[derive(Clone, Debug)]
+pub struct Translation {
+    pub text: String,
+    // The detected source language, if the backend reports it
+    pub source_lang: Option<String>,
+    // Romanized readings of the original text and the translation, if the backend reports them
+    pub source_romanization: Option<String>,
+    pub target_romanization: Option<String>,
+    // Name of the backend which did the translation
+    pub translator: &'static str,
+}
+
+/// Tries several backends in order, moving on to the next one whenever a backend fails
+pub struct FallbackTranslator {
+    translators: Vec<Box<dyn Translator>>,
+}
+
+#[async_trait]
+impl Translator for FallbackTranslator {
+    fn name(&self) -> &'static str {
+        self.translators[0].name()
+    }
+
+    fn max_text_length(&self) -> usize {
+        self.translators
+            .iter()
+            .map(|translator| translator.max_text_length())
+            .min()
+            .unwrap_or(0)
+    }
+
+    async fn translate(
+        &self,
+        source_lang: Option<&str>,
+        target_lang: &str,
+        text: &str,
+    ) -> TranslatorResult<Translation> {
+        let mut last_error = None;
+
+        for translator in &self.translators {
+            match translator.translate(source_lang, target_lang, text).await {
+                Ok(translation) => return Ok(translation),
+                Err(why) => {
+                    warn!("{} failed to translate: {}", translator.name(), why);
+                    last_error = Some(why);
+                }
+            }
+        }
+
+        Err(last_error.expect("Expected at least one translator"))
+    }
+
+    async fn romanize(
+        &self,
+        source_lang: Option<&str>,
+        text: &str,
+    ) -> TranslatorResult<Option<String>> {
+        let mut last_error = None;
+
+        // Not every backend supports romanization, so use the first one which does
+        for translator in &self.translators {
+            match translator.romanize(source_lang, text).await {
+                Ok(Some(romanization)) => return Ok(Some(romanization)),
+                Ok(None) => {}
+                Err(why) => {
+                    warn!("{} failed to romanize: {}", translator.name(), why);
+                    last_error = Some(why);
+                }
+            }
+        }
+
+        match last_error {
+            Some(why) => Err(why),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Creates the configured backends ("deepl", "libretranslate" and "google"), in that order of preference.
+/// DeepL needs an api key and LibreTranslate the url of an instance (and an api key, if the instance
+/// requires one), otherwise they're skipped.
+pub fn create_translator(client: &HttpClient, config: &TranslateConfig) -> Arc<dyn Translator> {
+    let mut translators: Vec<Box<dyn Translator>> = Vec::new();
+
+    for name in &config.translators {
+        match name.as_str() {
+            "deepl" => match &config.deepl_api_key {
+                Some(api_key) => translators.push(Box::new(DeepLTranslator::new(
+                    client.clone(),
+                    api_key.clone(),
+                ))),
+                None => info!("Skipping DeepL as translator, there's no api key configured"),
+            },
+            "libretranslate" => match &config.libretranslate_url {
+                Some(url) => translators.push(Box::new(LibreTranslateTranslator::new(
+                    client.clone(),
+                    url.clone(),
+                    config.libretranslate_api_key.clone(),
+                ))),
+                None => info!("Skipping LibreTranslate as translator, there's no url configured"),
+            },
+            "google" => translators.push(Box::new(GoogleTranslator::new(client.clone()))),
+            other => warn!("Unknown translator '{}'", other),
+        }
+    }
+
+    if translators.is_empty() {
+        warn!("No usable translator configured, falling back to Google Translate");
+        translators.push(Box::new(GoogleTranslator::new(client.clone())));
+    }
+
+    info!(
+        "Using {} as translators",
+        translators
+            .iter()
+            .map(|translator| translator.name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    Arc::new(FallbackTranslator { translators })
+}
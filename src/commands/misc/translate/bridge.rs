@@ -0,0 +1,381 @@
+use super::languages::{find_language, language_name};
+use super::{get_translator, translate_text, unknown_language_error};
+use crate::core::{
+    checks::ISADMIN_CHECK, config::get_config, consts::MAIN_COLOR, store::JsonStore,
+    text::split_text,
+};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use serenity::{
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    model::{
+        channel::Message,
+        id::{ChannelId, GuildId},
+    },
+    prelude::*,
+};
+use std::sync::Arc;
+
+// Discord's limits for webhook messages
+const MESSAGE_CONTENT_LIMIT: usize = 2000;
+const WEBHOOK_USERNAME_LIMIT: usize = 80;
+
+pub struct BridgesContainer;
+impl TypeMapKey for BridgesContainer {
+    type Value = Arc<RwLock<JsonStore<Bridges>>>;
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Bridges {
+    next_id: u64,
+    bridges: Vec<Bridge>,
+}
+
+/// Two linked channels, whose messages are relayed into each other translated
+#[derive(Serialize, Deserialize, Clone)]
+struct Bridge {
+    id: u64,
+    guild_id: GuildId,
+    ends: [BridgeEnd; 2],
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct BridgeEnd {
+    channel_id: ChannelId,
+    language: String,
+    // Webhook used to post relayed messages into this channel under the original author's name
+    webhook_id: u64,
+    webhook_token: String,
+}
+
+impl Bridge {
+    fn describe(&self) -> String {
+        format!(
+            "`{}`: <#{}> ({}) ↔ <#{}> ({})",
+            self.id,
+            self.ends[0].channel_id,
+            language_name(&self.ends[0].language),
+            self.ends[1].channel_id,
+            language_name(&self.ends[1].language)
+        )
+    }
+
+    /// The end messages posted in the given channel are relayed to, if the channel is part of the bridge
+    fn target_for(&self, channel_id: ChannelId) -> Option<&BridgeEnd> {
+        match &self.ends {
+            [from, to] if from.channel_id == channel_id => Some(to),
+            [to, from] if from.channel_id == channel_id => Some(to),
+            _ => None,
+        }
+    }
+}
+
+#[command]
+#[only_in(guilds)]
+#[checks(IsAdmin)]
+#[num_args(4)]
+#[description(
+    "Links two channels, so every message posted in one of them is relayed into the other, \
+    translated into that channel's language. Requires the Manage Webhooks permission for the bot."
+)]
+#[usage("<#channel> <language> <#channel> <language>")]
+#[example("#general-jp ja #general-en en")]
+pub async fn bridge(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap();
+
+    let mut ends = Vec::new();
+    for _ in 0..2 {
+        let channel_id = match args.single::<ChannelId>() {
+            Ok(channel_id) => channel_id,
+            Err(_) => {
+                return Err(CommandError::from(
+                    "You need to mention both channels, each followed by its language",
+                ))
+            }
+        };
+        let language_arg = args.single::<String>()?;
+        let language = match find_language(&language_arg) {
+            Some(language) => language,
+            None => {
+                return Err(unknown_language_error(
+                    get_config(ctx).await.prefix(),
+                    &language_arg,
+                ))
+            }
+        };
+
+        match channel_id.to_channel(ctx).await?.guild() {
+            Some(channel) if channel.guild_id == guild_id => {}
+            _ => {
+                return Err(CommandError::from(
+                    "Both channels need to be text channels of this server",
+                ))
+            }
+        }
+
+        ends.push((channel_id, language.code));
+    }
+
+    if ends[0].0 == ends[1].0 {
+        return Err(CommandError::from("A channel can't be bridged with itself"));
+    }
+
+    let mut bridge_ends: Vec<BridgeEnd> = Vec::new();
+    for (channel_id, language) in ends {
+        let webhook = match channel_id
+            .create_webhook(&ctx.http, "Translation bridge")
+            .await
+        {
+            Ok(webhook) => webhook,
+            Err(_) => {
+                // Don't leave the webhook of the first channel behind without a bridge
+                for end in &bridge_ends {
+                    let _ = ctx
+                        .http
+                        .delete_webhook_with_token(end.webhook_id, &end.webhook_token)
+                        .await;
+                }
+
+                return Err(CommandError::from(
+                    "I need the Manage Webhooks permission in both channels",
+                ));
+            }
+        };
+
+        bridge_ends.push(BridgeEnd {
+            channel_id,
+            language: language.to_string(),
+            webhook_id: webhook.id.0,
+            webhook_token: webhook.token.unwrap_or_default(),
+        });
+    }
+
+    let second = bridge_ends.pop().unwrap();
+    let first = bridge_ends.pop().unwrap();
+
+    let bridge = {
+        let store = get_store(ctx).await;
+        let mut store = store.write().await;
+
+        store.update(|data| {
+            let bridge = Bridge {
+                id: data.next_id,
+                guild_id,
+                ends: [first, second],
+            };
+
+            data.next_id += 1;
+            data.bridges.push(bridge.clone());
+
+            bridge
+        })?
+    };
+
+    let config = get_config(ctx).await;
+    let _ = msg
+        .channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.colour(MAIN_COLOR)
+                    .title("Channels bridged")
+                    .description(format!(
+                        "{}\n\nUse `{}translate unbridge {}` to remove the bridge again.",
+                        bridge.describe(),
+                        config.prefix(),
+                        bridge.id
+                    ))
+            })
+        })
+        .await;
+
+    Ok(())
+}
+
+#[command]
+#[only_in(guilds)]
+#[checks(IsAdmin)]
+#[num_args(1)]
+#[description("Removes a bridge between two channels")]
+#[usage("<bridge id>")]
+#[example("2")]
+pub async fn unbridge(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let id = match args.single::<u64>() {
+        Ok(id) => id,
+        Err(_) => {
+            return Err(CommandError::from(format!(
+                "You need to pass the id of the bridge, see `{}translate bridges`",
+                get_config(ctx).await.prefix()
+            )))
+        }
+    };
+
+    let removed = {
+        let store = get_store(ctx).await;
+        let mut store = store.write().await;
+
+        store.update(|data| {
+            let position = data
+                .bridges
+                .iter()
+                .position(|b| b.id == id && Some(b.guild_id) == msg.guild_id)?;
+
+            Some(data.bridges.remove(position))
+        })?
+    };
+
+    if let Some(bridge) = &removed {
+        for end in &bridge.ends {
+            let _ = ctx
+                .http
+                .delete_webhook_with_token(end.webhook_id, &end.webhook_token)
+                .await;
+        }
+    }
+
+    let _ = msg
+        .channel_id
+        .send_message(&ctx.http, |m| match removed {
+            Some(bridge) => m.content(format!("Removed the bridge {}", bridge.describe())),
+            None => m.content(format!("There's no bridge with the id `{}`", id)),
+        })
+        .await;
+
+    Ok(())
+}
+
+#[command]
+#[only_in(guilds)]
+#[description("Lists the bridged channels of this server")]
+pub async fn bridges(ctx: &Context, msg: &Message, _: Args) -> CommandResult {
+    let descriptions: Vec<String> = {
+        let store = get_store(ctx).await;
+        let store = store.read().await;
+
+        store
+            .get()
+            .bridges
+            .iter()
+            .filter(|b| Some(b.guild_id) == msg.guild_id)
+            .map(|b| b.describe())
+            .collect()
+    };
+
+    let config = get_config(ctx).await;
+    let _ = msg
+        .channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.colour(MAIN_COLOR).title("Bridged channels").description(
+                    match descriptions.is_empty() {
+                        true => format!(
+                            "There are no bridges yet. Create one with \
+                            `{}translate bridge <#channel> <language> <#channel> <language>`!",
+                            config.prefix()
+                        ),
+                        false => descriptions.join("\n"),
+                    },
+                )
+            })
+        })
+        .await;
+
+    Ok(())
+}
+
+/// Relays a message into all channels bridged with the one it was posted in
+pub async fn relay_bridged_message(ctx: &Context, msg: &Message) -> CommandResult {
+    // Webhook messages include the ones relayed by us, so skipping them prevents relay loops
+    if msg.webhook_id.is_some() || msg.author.bot || msg.guild_id.is_none() {
+        return Ok(());
+    }
+
+    let targets: Vec<BridgeEnd> = {
+        let store = get_store(ctx).await;
+        let store = store.read().await;
+
+        store
+            .get()
+            .bridges
+            .iter()
+            .filter_map(|b| b.target_for(msg.channel_id))
+            .cloned()
+            .collect()
+    };
+
+    if targets.is_empty() {
+        return Ok(());
+    }
+
+    // Commands are meant for the bot, not for the people on the other side
+    let config = get_config(ctx).await;
+    if config
+        .prefixes
+        .iter()
+        .any(|prefix| msg.content.starts_with(prefix.as_str()))
+    {
+        return Ok(());
+    }
+
+    let username: String = msg
+        .member
+        .as_ref()
+        .and_then(|member| member.nick.clone())
+        .unwrap_or_else(|| msg.author.name.clone())
+        .chars()
+        .take(WEBHOOK_USERNAME_LIMIT)
+        .collect();
+    let avatar_url = msg.author.face();
+    let translator = get_translator(ctx).await;
+
+    for target in targets {
+        let mut content = match msg.content.trim().is_empty() {
+            true => String::new(),
+            false => {
+                match translate_text(translator.as_ref(), None, &target.language, &msg.content)
+                    .await
+                {
+                    Ok(translation) => translation.text,
+                    // The other bridged channels can still get their translation
+                    Err(why) => {
+                        warn!(
+                            "Failed to translate a message for {}: {}",
+                            target.channel_id, why
+                        );
+                        continue;
+                    }
+                }
+            }
+        };
+
+        // Attachments can't be translated, but should still make it to the other side
+        for attachment in &msg.attachments {
+            content.push('\n');
+            content.push_str(&attachment.url);
+        }
+
+        for part in split_text(content.trim(), MESSAGE_CONTENT_LIMIT) {
+            let payload = json!({
+                "content": part,
+                "username": username,
+                "avatar_url": avatar_url,
+                // Relayed messages shouldn't ping anyone a second time
+                "allowed_mentions": { "parse": [] },
+            });
+
+            if let Some(map) = payload.as_object() {
+                ctx.http
+                    .execute_webhook(target.webhook_id, &target.webhook_token, false, map)
+                    .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn get_store(ctx: &Context) -> Arc<RwLock<JsonStore<Bridges>>> {
+    let data = ctx.data.read().await;
+
+    Arc::clone(data.get::<BridgesContainer>().unwrap())
+}
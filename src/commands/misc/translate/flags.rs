@@ -0,0 +1,165 @@
+// First of the 26 regional indicator symbols (🇦), which flag emojis are made of
+const REGIONAL_INDICATOR_A: u32 = 0x1F1E6;
+
+// Main language of each country, by ISO 3166-1 country code.
+// Countries with several equally common languages use the one most likely meant.
+static COUNTRY_LANGUAGES: &[(&str, &str)] = &[
+    ("AD", "ca"),
+    ("AE", "ar"),
+    ("AF", "ps"),
+    ("AG", "en"),
+    ("AL", "sq"),
+    ("AM", "hy"),
+    ("AO", "pt"),
+    ("AR", "es"),
+    ("AT", "de"),
+    ("AU", "en"),
+    ("AZ", "az"),
+    ("BA", "bs"),
+    ("BD", "bn"),
+    ("BE", "nl"),
+    ("BG", "bg"),
+    ("BH", "ar"),
+    ("BO", "es"),
+    ("BR", "pt"),
+    ("BY", "be"),
+    ("CA", "en"),
+    ("CH", "de"),
+    ("CL", "es"),
+    ("CN", "zh-CN"),
+    ("CO", "es"),
+    ("CR", "es"),
+    ("CU", "es"),
+    ("CY", "el"),
+    ("CZ", "cs"),
+    ("DE", "de"),
+    ("DK", "da"),
+    ("DO", "es"),
+    ("DZ", "ar"),
+    ("EC", "es"),
+    ("EE", "et"),
+    ("EG", "ar"),
+    ("ES", "es"),
+    ("ET", "am"),
+    ("FI", "fi"),
+    ("FR", "fr"),
+    ("GB", "en"),
+    ("GE", "ka"),
+    ("GR", "el"),
+    ("GT", "es"),
+    ("HK", "zh-TW"),
+    ("HN", "es"),
+    ("HR", "hr"),
+    ("HT", "ht"),
+    ("HU", "hu"),
+    ("ID", "id"),
+    ("IE", "en"),
+    ("IL", "iw"),
+    ("IN", "hi"),
+    ("IQ", "ar"),
+    ("IR", "fa"),
+    ("IS", "is"),
+    ("IT", "it"),
+    ("JM", "en"),
+    ("JO", "ar"),
+    ("JP", "ja"),
+    ("KE", "sw"),
+    ("KG", "ky"),
+    ("KH", "km"),
+    ("KP", "ko"),
+    ("KR", "ko"),
+    ("KW", "ar"),
+    ("KZ", "kk"),
+    ("LA", "lo"),
+    ("LB", "ar"),
+    ("LK", "si"),
+    ("LT", "lt"),
+    ("LU", "lb"),
+    ("LV", "lv"),
+    ("LY", "ar"),
+    ("MA", "ar"),
+    ("MD", "ro"),
+    ("ME", "sr"),
+    ("MG", "mg"),
+    ("MK", "mk"),
+    ("MM", "my"),
+    ("MN", "mn"),
+    ("MO", "zh-TW"),
+    ("MT", "mt"),
+    ("MX", "es"),
+    ("MY", "ms"),
+    ("NG", "en"),
+    ("NI", "es"),
+    ("NL", "nl"),
+    ("NO", "no"),
+    ("NP", "ne"),
+    ("NZ", "en"),
+    ("OM", "ar"),
+    ("PA", "es"),
+    ("PE", "es"),
+    ("PH", "tl"),
+    ("PK", "ur"),
+    ("PL", "pl"),
+    ("PR", "es"),
+    ("PT", "pt"),
+    ("PY", "es"),
+    ("QA", "ar"),
+    ("RO", "ro"),
+    ("RS", "sr"),
+    ("RU", "ru"),
+    ("RW", "rw"),
+    ("SA", "ar"),
+    ("SE", "sv"),
+    ("SG", "en"),
+    ("SI", "sl"),
+    ("SK", "sk"),
+    ("SO", "so"),
+    ("SV", "es"),
+    ("SY", "ar"),
+    ("TH", "th"),
+    ("TJ", "tg"),
+    ("TM", "tk"),
+    ("TN", "ar"),
+    ("TR", "tr"),
+    ("TW", "zh-TW"),
+    ("TZ", "sw"),
+    ("UA", "uk"),
+    ("UG", "en"),
+    ("US", "en"),
+    ("UY", "es"),
+    ("UZ", "uz"),
+    ("VE", "es"),
+    ("VN", "vi"),
+    ("YE", "ar"),
+    ("ZA", "af"),
+    ("ZW", "sn"),
+];
+
+/// Language code of the country a flag emoji stands for, e.g. "ja" for 🇯🇵
+pub fn language_for_flag(emoji: &str) -> Option<&'static str> {
+    let country_code = country_code(emoji)?;
+
+    COUNTRY_LANGUAGES
+        .iter()
+        .find(|(country, _)| *country == country_code)
+        .map(|(_, language)| *language)
+}
+
+/// Turns a flag emoji back into the two letter country code it's made of
+fn country_code(emoji: &str) -> Option<String> {
+    let letters = emoji
+        .chars()
+        .map(|c| {
+            let offset = (c as u32).checked_sub(REGIONAL_INDICATOR_A)?;
+            match offset < 26 {
+                true => Some((b'A' + offset as u8) as char),
+                false => None,
+            }
+        })
+        .collect::<Option<String>>()?;
+
+    match letters.len() {
+        2 => Some(letters),
+        _ => None,
+    }
+}
@@ -0,0 +1,264 @@
+use crate::core::fuzzy;
+
+pub struct Language {
+    // Code used by the translation api, ISO 639-1 where the language has one
+    pub code: &'static str,
+    pub iso639_2: Option<&'static str>,
+    pub name: &'static str,
+    pub native_name: &'static str,
+    // Other names and codes the language is commonly known by
+    pub aliases: &'static [&'static str],
+}
+
+const fn language(
+    code: &'static str,
+    iso639_2: Option<&'static str>,
+    name: &'static str,
+    native_name: &'static str,
+    aliases: &'static [&'static str],
+) -> Language {
+    Language {
+        code,
+        iso639_2,
+        name,
+        native_name,
+        aliases,
+    }
+}
+
+pub static LANGUAGES: &[Language] = &[
+    language("af", Some("afr"), "Afrikaans", "Afrikaans", &[]),
+    language("sq", Some("sqi"), "Albanian", "Shqip", &["alb"]),
+    language("am", Some("amh"), "Amharic", "አማርኛ", &[]),
+    language("ar", Some("ara"), "Arabic", "العربية", &[]),
+    language("hy", Some("hye"), "Armenian", "Հայերեն", &["arm"]),
+    language("az", Some("aze"), "Azerbaijani", "Azərbaycanca", &["azeri"]),
+    language("eu", Some("eus"), "Basque", "Euskara", &["baq"]),
+    language("be", Some("bel"), "Belarusian", "Беларуская", &[]),
+    language("bn", Some("ben"), "Bengali", "বাংলা", &["bangla"]),
+    language("bs", Some("bos"), "Bosnian", "Bosanski", &[]),
+    language("bg", Some("bul"), "Bulgarian", "Български", &[]),
+    language("ca", Some("cat"), "Catalan", "Català", &[]),
+    language("ceb", Some("ceb"), "Cebuano", "Cebuano", &[]),
+    language(
+        "ny",
+        Some("nya"),
+        "Chichewa",
+        "Chichewa",
+        &["nyanja", "chewa"],
+    ),
+    language(
+        "zh-CN",
+        Some("zho"),
+        "Chinese (Simplified)",
+        "简体中文",
+        &["zh", "zh-hans", "chinese", "mandarin", "chi", "cn"],
+    ),
+    language(
+        "zh-TW",
+        None,
+        "Chinese (Traditional)",
+        "繁體中文",
+        &["zh-hant", "traditional-chinese", "tw"],
+    ),
+    language("co", Some("cos"), "Corsican", "Corsu", &[]),
+    language("hr", Some("hrv"), "Croatian", "Hrvatski", &[]),
+    language("cs", Some("ces"), "Czech", "Čeština", &["cze"]),
+    language("da", Some("dan"), "Danish", "Dansk", &[]),
+    language(
+        "nl",
+        Some("nld"),
+        "Dutch",
+        "Nederlands",
+        &["dut", "flemish"],
+    ),
+    language("en", Some("eng"), "English", "English", &[]),
+    language("eo", Some("epo"), "Esperanto", "Esperanto", &[]),
+    language("et", Some("est"), "Estonian", "Eesti", &[]),
+    language(
+        "tl",
+        Some("tgl"),
+        "Filipino",
+        "Tagalog",
+        &["tagalog", "fil"],
+    ),
+    language("fi", Some("fin"), "Finnish", "Suomi", &[]),
+    language("fr", Some("fra"), "French", "Français", &["fre"]),
+    language("fy", Some("fry"), "Frisian", "Frysk", &[]),
+    language("gl", Some("glg"), "Galician", "Galego", &[]),
+    language("ka", Some("kat"), "Georgian", "ქართული", &["geo"]),
+    language("de", Some("deu"), "German", "Deutsch", &["ger"]),
+    language("el", Some("ell"), "Greek", "Ελληνικά", &["gre"]),
+    language("gu", Some("guj"), "Gujarati", "ગુજરાતી", &[]),
+    language(
+        "ht",
+        Some("hat"),
+        "Haitian Creole",
+        "Kreyòl ayisyen",
+        &["haitian", "creole"],
+    ),
+    language("ha", Some("hau"), "Hausa", "Hausa", &[]),
+    language("haw", Some("haw"), "Hawaiian", "ʻŌlelo Hawaiʻi", &[]),
+    language("iw", Some("heb"), "Hebrew", "עברית", &["he"]),
+    language("hi", Some("hin"), "Hindi", "हिन्दी", &[]),
+    language("hmn", Some("hmn"), "Hmong", "Hmoob", &[]),
+    language("hu", Some("hun"), "Hungarian", "Magyar", &[]),
+    language("is", Some("isl"), "Icelandic", "Íslenska", &["ice"]),
+    language("ig", Some("ibo"), "Igbo", "Igbo", &[]),
+    language("id", Some("ind"), "Indonesian", "Bahasa Indonesia", &[]),
+    language("ga", Some("gle"), "Irish", "Gaeilge", &[]),
+    language("it", Some("ita"), "Italian", "Italiano", &[]),
+    language("ja", Some("jpn"), "Japanese", "日本語", &["jp"]),
+    language("jw", Some("jav"), "Javanese", "Basa Jawa", &["jv"]),
+    language("kn", Some("kan"), "Kannada", "ಕನ್ನಡ", &[]),
+    language("kk", Some("kaz"), "Kazakh", "Қазақ тілі", &[]),
+    language("km", Some("khm"), "Khmer", "ខ្មែរ", &["cambodian"]),
+    language("rw", Some("kin"), "Kinyarwanda", "Ikinyarwanda", &[]),
+    language("ko", Some("kor"), "Korean", "한국어", &["kr"]),
+    language("ku", Some("kur"), "Kurdish", "Kurdî", &[]),
+    language("ky", Some("kir"), "Kyrgyz", "Кыргызча", &["kirghiz"]),
+    language("lo", Some("lao"), "Lao", "ລາວ", &["laotian"]),
+    language("la", Some("lat"), "Latin", "Latina", &[]),
+    language("lv", Some("lav"), "Latvian", "Latviešu", &[]),
+    language("lt", Some("lit"), "Lithuanian", "Lietuvių", &[]),
+    language("lb", Some("ltz"), "Luxembourgish", "Lëtzebuergesch", &[]),
+    language("mk", Some("mkd"), "Macedonian", "Македонски", &["mac"]),
+    language("mg", Some("mlg"), "Malagasy", "Malagasy", &[]),
+    language("ms", Some("msa"), "Malay", "Bahasa Melayu", &["may"]),
+    language("ml", Some("mal"), "Malayalam", "മലയാളം", &[]),
+    language("mt", Some("mlt"), "Maltese", "Malti", &[]),
+    language("mi", Some("mri"), "Maori", "Māori", &["mao"]),
+    language("mr", Some("mar"), "Marathi", "मराठी", &[]),
+    language("mn", Some("mon"), "Mongolian", "Монгол", &[]),
+    language("my", Some("mya"), "Myanmar", "မြန်မာ", &["burmese", "bur"]),
+    language("ne", Some("nep"), "Nepali", "नेपाली", &[]),
+    language("no", Some("nor"), "Norwegian", "Norsk", &["nb", "nn"]),
+    language("or", Some("ori"), "Odia", "ଓଡ଼ିଆ", &["oriya"]),
+    language("ps", Some("pus"), "Pashto", "پښتو", &[]),
+    language("fa", Some("fas"), "Persian", "فارسی", &["farsi", "per"]),
+    language("pl", Some("pol"), "Polish", "Polski", &[]),
+    language("pt", Some("por"), "Portuguese", "Português", &[]),
+    language("pa", Some("pan"), "Punjabi", "ਪੰਜਾਬੀ", &[]),
+    language("ro", Some("ron"), "Romanian", "Română", &["rum"]),
+    language("ru", Some("rus"), "Russian", "Русский", &[]),
+    language("sm", Some("smo"), "Samoan", "Gagana Samoa", &[]),
+    language("gd", Some("gla"), "Scots Gaelic", "Gàidhlig", &["gaelic"]),
+    language("sr", Some("srp"), "Serbian", "Српски", &[]),
+    language("st", Some("sot"), "Sesotho", "Sesotho", &["sotho"]),
+    language("sn", Some("sna"), "Shona", "chiShona", &[]),
+    language("sd", Some("snd"), "Sindhi", "سنڌي", &[]),
+    language("si", Some("sin"), "Sinhala", "සිංහල", &["sinhalese"]),
+    language("sk", Some("slk"), "Slovak", "Slovenčina", &["slo"]),
+    language("sl", Some("slv"), "Slovenian", "Slovenščina", &["slovene"]),
+    language("so", Some("som"), "Somali", "Soomaali", &[]),
+    language("es", Some("spa"), "Spanish", "Español", &["castilian"]),
+    language("su", Some("sun"), "Sundanese", "Basa Sunda", &[]),
+    language("sw", Some("swa"), "Swahili", "Kiswahili", &[]),
+    language("sv", Some("swe"), "Swedish", "Svenska", &[]),
+    language("tg", Some("tgk"), "Tajik", "Тоҷикӣ", &[]),
+    language("ta", Some("tam"), "Tamil", "தமிழ்", &[]),
+    language("tt", Some("tat"), "Tatar", "Татар", &[]),
+    language("te", Some("tel"), "Telugu", "తెలుగు", &[]),
+    language("th", Some("tha"), "Thai", "ไทย", &[]),
+    language("tr", Some("tur"), "Turkish", "Türkçe", &[]),
+    language("tk", Some("tuk"), "Turkmen", "Türkmençe", &[]),
+    language("uk", Some("ukr"), "Ukrainian", "Українська", &[]),
+    language("ur", Some("urd"), "Urdu", "اردو", &[]),
+    language("ug", Some("uig"), "Uyghur", "ئۇيغۇرچە", &["uighur"]),
+    language("uz", Some("uzb"), "Uzbek", "Oʻzbekcha", &[]),
+    language("vi", Some("vie"), "Vietnamese", "Tiếng Việt", &[]),
+    language("cy", Some("cym"), "Welsh", "Cymraeg", &["wel"]),
+    language("xh", Some("xho"), "Xhosa", "isiXhosa", &[]),
+    language("yi", Some("yid"), "Yiddish", "ייִדיש", &[]),
+    language("yo", Some("yor"), "Yoruba", "Yorùbá", &[]),
+    language("zu", Some("zul"), "Zulu", "isiZulu", &[]),
+];
+
+impl Language {
+    /// Every name and code this language can be referred to by
+    pub fn identifiers(&self) -> Vec<&'static str> {
+        let mut identifiers = vec![self.code, self.name, self.native_name];
+        identifiers.extend(self.iso639_2);
+        identifiers.extend(self.aliases);
+
+        identifiers
+    }
+}
+
+/// Finds a language by any of its codes, names or aliases, ignoring case
+pub fn find_language(input: &str) -> Option<&'static Language> {
+    let input = input.trim().to_lowercase();
+
+    LANGUAGES.iter().find(|language| {
+        language
+            .identifiers()
+            .iter()
+            .any(|identifier| identifier.to_lowercase() == input)
+    })
+}
+
+/// Suggests the languages the user most likely meant, for when `find_language` found nothing
+pub fn suggest_languages(input: &str) -> Vec<&'static Language> {
+    let candidates = LANGUAGES.iter().flat_map(|language| language.identifiers());
+
+    let mut suggestions: Vec<&'static Language> = Vec::new();
+
+    for language in fuzzy::suggest(input, candidates, 10)
+        .into_iter()
+        .filter_map(find_language)
+    {
+        if !suggestions.iter().any(|s| s.code == language.code) {
+            suggestions.push(language);
+        }
+    }
+
+    suggestions.truncate(3);
+
+    suggestions
+}
+
+/// Name of the language with the given api code, falling back to the code itself
+pub fn language_name(code: &str) -> String {
+    match find_language(code) {
+        Some(language) => language.name.to_string(),
+        None => code.to_ascii_uppercase(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(input: &str) -> Option<&'static str> {
+        find_language(input).map(|language| language.code)
+    }
+
+    #[test]
+    fn finds_languages_by_any_identifier() {
+        assert_eq!(code("German"), Some("de"));
+        assert_eq!(code(" deutsch "), Some("de"));
+        assert_eq!(code("ger"), Some("de"));
+        assert_eq!(code("zh-TW"), Some("zh-TW"));
+        assert_eq!(code("zh-tw"), Some("zh-TW"));
+        assert_eq!(code("mandarin"), Some("zh-CN"));
+        assert_eq!(code("Klingon"), None);
+    }
+
+    #[test]
+    fn suggests_languages_for_typos() {
+        let suggestions: Vec<&str> = suggest_languages("Germn")
+            .iter()
+            .map(|language| language.code)
+            .collect();
+
+        assert_eq!(suggestions.first(), Some(&"de"));
+        assert!(suggestions.len() <= 3);
+        assert!(suggest_languages("qqqqqqqqqq").is_empty());
+    }
+
+    #[test]
+    fn names_languages_by_code() {
+        assert_eq!(language_name("ja"), "Japanese");
+        assert_eq!(language_name("xx"), "XX");
+    }
+}
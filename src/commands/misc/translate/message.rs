@@ -0,0 +1,129 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serenity::{
+    framework::standard::CommandError,
+    model::{
+        channel::Message,
+        id::{ChannelId, GuildId, MessageId},
+    },
+    prelude::Context,
+};
+
+lazy_static! {
+    // Regex to parse links to discord messages, e.g. https://discord.com/channels/<guild>/<channel>/<message>
+    static ref MESSAGE_LINK_REGEX: Regex = Regex::new(
+        r"^<?https://(?:(?:ptb|canary)\.)?discord(?:app)?\.com/channels/(\d+|@me)/(\d+)/(\d+)>?$"
+    )
+    .unwrap();
+}
+
+/// Determines the text to translate. That's the given text, unless it's empty (in which case
+/// the message replied to is translated) or a message link (in which case the linked message is).
+pub async fn find_text_to_translate(
+    ctx: &Context,
+    msg: &Message,
+    text: &str,
+) -> Result<String, CommandError> {
+    let text = text.trim();
+
+    if let Some(captures) = MESSAGE_LINK_REGEX.captures(text) {
+        let guild_id = captures[1].parse().ok().map(GuildId);
+        let channel_id = ChannelId(captures[2].parse()?);
+        let message_id = MessageId(captures[3].parse()?);
+
+        check_can_read_channel(ctx, msg, guild_id, channel_id).await?;
+
+        let linked = channel_id
+            .message(&ctx.http, message_id)
+            .await
+            .map_err(|_| "I couldn't find the linked message")?;
+
+        return message_text(&linked);
+    }
+
+    if !text.is_empty() {
+        return Ok(text.to_string());
+    }
+
+    let referenced = match (&msg.referenced_message, &msg.message_reference) {
+        (Some(referenced), _) => Some(*referenced.clone()),
+        // Discord doesn't always include the referenced message, so fetch it ourselves then
+        (None, Some(reference)) => match reference.message_id {
+            Some(message_id) => reference
+                .channel_id
+                .message(&ctx.http, message_id)
+                .await
+                .ok(),
+            None => None,
+        },
+        (None, None) => None,
+    };
+
+    match referenced {
+        Some(referenced) => message_text(&referenced),
+        None => Err(CommandError::from(
+            "There needs to be a text to be translated! \
+            You can also reply to a message or give a message link instead.",
+        )),
+    }
+}
+
+/// Collects the text of a message, including the text of its embeds
+pub fn message_text(message: &Message) -> Result<String, CommandError> {
+    let mut parts: Vec<&str> = Vec::new();
+
+    if !message.content.trim().is_empty() {
+        parts.push(&message.content);
+    }
+
+    for embed in &message.embeds {
+        parts.extend(embed.title.as_deref());
+        parts.extend(embed.description.as_deref());
+
+        for field in &embed.fields {
+            parts.push(&field.name);
+            parts.push(&field.value);
+        }
+    }
+
+    match parts.is_empty() {
+        true => Err(CommandError::from("That message doesn't contain any text")),
+        false => Ok(parts.join("\n")),
+    }
+}
+
+/// Makes sure a linked message can't be used to read channels the author doesn't have access to
+async fn check_can_read_channel(
+    ctx: &Context,
+    msg: &Message,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+) -> Result<(), CommandError> {
+    // Direct messages can only be linked from within the same conversation
+    if guild_id.is_none() {
+        return match channel_id == msg.channel_id {
+            true => Ok(()),
+            false => Err(CommandError::from(
+                "I can only translate linked messages from this server",
+            )),
+        };
+    }
+
+    let channel = match channel_id.to_channel(ctx).await?.guild() {
+        Some(channel) if Some(channel.guild_id) == msg.guild_id => channel,
+        _ => {
+            return Err(CommandError::from(
+                "I can only translate linked messages from this server",
+            ))
+        }
+    };
+
+    let permissions = channel.permissions_for_user(ctx, msg.author.id).await?;
+
+    match permissions.read_messages() && permissions.read_message_history() {
+        true => Ok(()),
+        false => Err(CommandError::from(
+            "You can't read the channel of the linked message",
+        )),
+    }
+}
@@ -0,0 +1,546 @@
+pub mod backend;
+mod bridge;
+mod flags;
+mod languages;
+mod message;
+mod reactions;
+mod romanize;
+
+pub use self::bridge::{relay_bridged_message, BridgesContainer};
+pub use self::reactions::{translate_flag_reaction, FlagTranslationsContainer};
+pub use self::romanize::ROMANIZE_COMMAND;
+
+use serenity::{
+    builder::CreateEmbed,
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    model::channel::Message,
+    prelude::Context,
+};
+use std::sync::Arc;
+
+use self::backend::{
+    TranslateError, Translation, Translator, TranslatorContainer, TranslatorResult,
+};
+use self::bridge::{BRIDGES_COMMAND, BRIDGE_COMMAND, UNBRIDGE_COMMAND};
+use self::languages::{find_language, language_name, suggest_languages, LANGUAGES};
+use self::message::find_text_to_translate;
+use self::reactions::REACTIONS_COMMAND;
+use crate::core::{
+    config::get_config,
+    consts::MAIN_COLOR,
+    sink::{ChannelSink, MessageSink, OutgoingMessage},
+    text::split_text,
+};
+
+// Discord's limits for embed descriptions, field values and all text of an embed together
+const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+const EMBED_FIELD_LIMIT: usize = 1024;
+const EMBED_TOTAL_LIMIT: usize = 6000;
+
+#[command]
+#[description(
+    "Translates a given text into the target language given as the first argument. \
+        You can optionally prefix the source language as first argument, \
+        otherwise it will be auto detected. \
+        Instead of a text, you can also reply to a message or give a message link to translate it. \
+        Pass `-r` first to also get the romanized readings, e.g. romaji for Japanese. \
+        Languages can be given by code or name, the `languages` subcommand lists all of them."
+)]
+#[usage("[-r] [source language] <target language> <text | message link>")]
+#[example("en こんにちは！")]
+#[example("de en Guten Abend!")]
+#[example("japanese Good morning!")]
+#[example("en https://discord.com/channels/123/456/789")]
+#[example("-r ja Good evening!")]
+#[sub_commands(languages, reactions, bridge, unbridge, bridges)]
+pub async fn translate(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    // Romanized readings are only shown on request, as they'd clutter up most translations
+    let show_romanization = match args.current() {
+        Some("-r") | Some("--romanize") => {
+            args.advance();
+            true
+        }
+        _ => false,
+    };
+
+    let first_arg = args.single::<String>()?;
+    let second_arg = args.single::<String>().ok();
+
+    // Get the target lang (or source lang if second language is given)
+    let mut target_lang = match find_language(&first_arg) {
+        Some(language) => language.code,
+        None => {
+            return Err(unknown_language_error(
+                get_config(ctx).await.prefix(),
+                &first_arg,
+            ))
+        }
+    };
+
+    // Try to grab a second language parameter. On success, use that as the target_lang and the
+    // initial first parameter as source language (i.e. switch from <target> <text> to <source> <target> <text>)
+    let source_lang = match second_arg.as_deref().and_then(find_language) {
+        Some(language) => {
+            let lang = language.code;
+            // When the second argument is a language,
+            // swap first and second arguments
+            let target_lang_copy = target_lang;
+            target_lang = lang;
+            Some(target_lang_copy)
+        }
+        None => {
+            // Else write this argument back to args, as it's part of the translation string!
+            if second_arg.is_some() {
+                args.rewind();
+            }
+            None
+        }
+    };
+
+    // Without any text, this translates the message replied to or linked instead
+    let text = find_text_to_translate(ctx, msg, args.rest()).await?;
+    let translator = get_translator(ctx).await;
+    let sink = ChannelSink::new(&ctx.http, msg.channel_id);
+
+    send_translated_text(
+        &sink,
+        translator.as_ref(),
+        source_lang,
+        target_lang,
+        &text,
+        show_romanization,
+    )
+    .await
+}
+
+/// Translates the text and sends the translation, with its romanized readings if requested
+async fn send_translated_text(
+    sink: &dyn MessageSink,
+    translator: &dyn Translator,
+    source_lang: Option<&str>,
+    target_lang: &str,
+    text: &str,
+    show_romanization: bool,
+) -> CommandResult {
+    let mut translation = match translate_text(translator, source_lang, target_lang, text).await {
+        Ok(translation) => translation,
+        Err(why) => return reply_with_error(sink, why).await,
+    };
+
+    // The backend which translated might not support romanization, so ask the others
+    if show_romanization {
+        if translation.source_romanization.is_none() {
+            translation.source_romanization = romanize_text(translator, source_lang, text)
+                .await
+                .unwrap_or_default();
+        }
+        if translation.target_romanization.is_none() {
+            translation.target_romanization =
+                romanize_text(translator, Some(target_lang), &translation.text)
+                    .await
+                    .unwrap_or_default();
+        }
+    }
+
+    send_translation(sink, target_lang, &translation, show_romanization).await;
+
+    Ok(())
+}
+
+/// Translates a text of any length, splitting it up if it's too long for a single request
+pub(super) async fn translate_text(
+    translator: &dyn Translator,
+    source_lang: Option<&str>,
+    target_lang: &str,
+    text: &str,
+) -> TranslatorResult<Translation> {
+    let mut translation: Option<Translation> = None;
+
+    for chunk in split_text(text, translator.max_text_length()) {
+        let translated_chunk = translator
+            .translate(source_lang, target_lang, chunk.trim())
+            .await?;
+        let separator = chunk_separator(&chunk);
+
+        match &mut translation {
+            None => {
+                let mut first_chunk = translated_chunk;
+                first_chunk.text.push_str(separator);
+                translation = Some(first_chunk);
+            }
+            Some(translation) => {
+                translation.text.push_str(&translated_chunk.text);
+                translation.text.push_str(separator);
+                append_romanization(
+                    &mut translation.source_romanization,
+                    translated_chunk.source_romanization,
+                );
+                append_romanization(
+                    &mut translation.target_romanization,
+                    translated_chunk.target_romanization,
+                );
+            }
+        }
+    }
+
+    let mut translation =
+        translation.ok_or_else(|| TranslateError::Rejected("the text is empty".to_string()))?;
+    translation.text.truncate(translation.text.trim_end().len());
+
+    Ok(translation)
+}
+
+/// Romanizes a text of any length, splitting it up if it's too long for a single request
+pub(super) async fn romanize_text(
+    translator: &dyn Translator,
+    source_lang: Option<&str>,
+    text: &str,
+) -> TranslatorResult<Option<String>> {
+    let mut romanization = None;
+
+    for chunk in split_text(text, translator.max_text_length()) {
+        let romanized_chunk = translator.romanize(source_lang, chunk.trim()).await?;
+        append_romanization(&mut romanization, romanized_chunk);
+    }
+
+    Ok(romanization)
+}
+
+fn append_romanization(romanization: &mut Option<String>, chunk: Option<String>) {
+    match (romanization.as_mut(), chunk) {
+        (Some(romanization), Some(chunk)) => {
+            romanization.push(' ');
+            romanization.push_str(&chunk);
+        }
+        (None, Some(chunk)) => *romanization = Some(chunk),
+        (_, None) => {}
+    }
+}
+
+/// Sends a translation, split up into multiple embeds if it doesn't fit into one
+pub(super) async fn send_translation(
+    sink: &dyn MessageSink,
+    target_lang: &str,
+    translation: &Translation,
+    show_romanization: bool,
+) {
+    let title = format!(
+        "Translation from {} -> {}",
+        translation
+            .source_lang
+            .as_deref()
+            .map_or("Unknown".to_string(), language_name),
+        language_name(target_lang)
+    );
+
+    let readings: Vec<(&str, String, bool)> = match show_romanization {
+        true => vec![
+            ("Reading of the original", &translation.source_romanization),
+            (
+                "Reading of the translation",
+                &translation.target_romanization,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, reading)| Some((name, shorten(reading.as_ref()?), false)))
+        .collect(),
+        false => Vec::new(),
+    };
+    let readings_length: usize = readings
+        .iter()
+        .map(|(name, reading, _)| name.chars().count() + reading.chars().count())
+        .sum();
+
+    let parts = split_text(&translation.text, EMBED_DESCRIPTION_LIMIT);
+    let part_count = parts.len();
+    let mut readings_sent = readings.is_empty();
+
+    for (i, part) in parts.into_iter().enumerate() {
+        let footer = match part_count {
+            1 => format!("Translated by {}", translation.translator),
+            _ => format!(
+                "Translated by {} | Part {}/{}",
+                translation.translator,
+                i + 1,
+                part_count
+            ),
+        };
+
+        let mut embed = CreateEmbed::default();
+        embed.colour(MAIN_COLOR);
+
+        let mut length = part.chars().count() + footer.chars().count();
+        if i == 0 {
+            length += title.chars().count();
+            embed.title(&title);
+        }
+        // Readings go below the end of the translation, if they fit into the same embed
+        if i + 1 == part_count && !readings_sent && length + readings_length <= EMBED_TOTAL_LIMIT {
+            embed.fields(readings.clone());
+            readings_sent = true;
+        }
+
+        embed.description(part).footer(|f| f.text(footer));
+
+        let _ = sink.send(OutgoingMessage::embed(embed)).await;
+    }
+
+    // Otherwise they get an embed of their own
+    if !readings_sent {
+        let mut embed = CreateEmbed::default();
+        embed
+            .colour(MAIN_COLOR)
+            .fields(readings)
+            .footer(|f| f.text(format!("Translated by {}", translation.translator)));
+
+        let _ = sink.send(OutgoingMessage::embed(embed)).await;
+    }
+}
+
+pub(super) async fn reply_with_error(sink: &dyn MessageSink, why: TranslateError) -> CommandResult {
+    if !why.is_user_facing() {
+        return Err(why.into());
+    }
+
+    let mut embed = CreateEmbed::default();
+    embed
+        .colour(MAIN_COLOR)
+        .title("Lost in translation :(")
+        .description(why.to_string());
+
+    let _ = sink.send(OutgoingMessage::embed(embed)).await;
+
+    Ok(())
+}
+
+pub(super) async fn get_translator(ctx: &Context) -> Arc<dyn Translator> {
+    let data = ctx.data.read().await;
+
+    Arc::clone(data.get::<TranslatorContainer>().unwrap())
+}
+
+/// Cuts a text down to the maximum length of an embed field
+fn shorten(text: &str) -> String {
+    match text.chars().count() > EMBED_FIELD_LIMIT {
+        true => text.chars().take(EMBED_FIELD_LIMIT - 1).collect::<String>() + "…",
+        false => text.to_string(),
+    }
+}
+
+/// Whitespace to put after the translation of a chunk, matching the one that ended the original chunk
+fn chunk_separator(chunk: &str) -> &'static str {
+    let trailing = &chunk[chunk.trim_end().len()..];
+
+    if trailing.contains('\n') {
+        "\n"
+    } else if trailing.is_empty() {
+        ""
+    } else {
+        " "
+    }
+}
+
+#[command]
+#[description("Lists all languages that can be translated from and to.")]
+pub async fn languages(ctx: &Context, msg: &Message) -> CommandResult {
+    // Fill up fields line by line, as a single one can't hold all languages
+    let mut fields: Vec<String> = vec![String::new()];
+
+    for language in LANGUAGES {
+        let line = format!("`{}` {}\n", language.code, language.name);
+
+        if fields.last().unwrap().len() + line.len() > EMBED_FIELD_LIMIT {
+            fields.push(String::new());
+        }
+
+        fields.last_mut().unwrap().push_str(&line);
+    }
+
+    let _ = msg
+        .channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.colour(MAIN_COLOR)
+                    .title("Supported languages")
+                    .description(
+                        "Languages can be given by their code, english or native name, \
+                        e.g. `de`, `German` or `Deutsch`.",
+                    )
+                    .fields(fields.into_iter().map(|field| ("\u{200b}", field, true)))
+            })
+        })
+        .await;
+
+    Ok(())
+}
+
+pub(super) fn unknown_language_error(prefix: &str, input: &str) -> CommandError {
+    let suggestions: Vec<String> = suggest_languages(input)
+        .iter()
+        .map(|language| format!("`{}` ({})", language.code, language.name))
+        .collect();
+
+    if suggestions.is_empty() {
+        CommandError::from(format!(
+            "I don't know a language called '{}'. See `{}translate languages` for all of them.",
+            input, prefix
+        ))
+    } else {
+        CommandError::from(format!(
+            "I don't know a language called '{}'. Did you mean {}?",
+            input,
+            suggestions.join(", ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::backend::create_translator;
+    use super::*;
+    use crate::core::{
+        config::TranslateConfig,
+        http::HttpClient,
+        testing::{
+            embed_fields, embed_footer, embed_str, fixture, MockResponse, MockServer, RecordingSink,
+        },
+    };
+
+    fn libretranslate(server: &MockServer) -> Arc<dyn Translator> {
+        let config = TranslateConfig {
+            translators: vec!["libretranslate".to_string()],
+            libretranslate_url: Some(server.url().to_string()),
+            ..Default::default()
+        };
+
+        create_translator(&HttpClient::new(), &config)
+    }
+
+    #[tokio::test]
+    async fn sends_the_translation() {
+        let server = MockServer::start();
+        server.mock(
+            "POST",
+            "/translate",
+            MockResponse::new(200, fixture("translate/libretranslate.json")),
+        );
+        let translator = libretranslate(&server);
+        let sink = RecordingSink::new();
+
+        send_translated_text(
+            &sink,
+            translator.as_ref(),
+            None,
+            "en",
+            "Guten Morgen, wie geht es dir?",
+            false,
+        )
+        .await
+        .unwrap();
+
+        let messages = sink.messages();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            embed_str(&messages[0], "title"),
+            Some("Translation from German -> English")
+        );
+        assert_eq!(
+            embed_str(&messages[0], "description"),
+            Some("Good morning, how are you?")
+        );
+        assert_eq!(
+            embed_footer(&messages[0]),
+            Some("Translated by LibreTranslate")
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert!(requests[0].body.contains(r#""source":"auto""#));
+        assert!(requests[0].body.contains(r#""target":"en""#));
+    }
+
+    fn long_translation(romanization: Option<String>) -> Translation {
+        Translation {
+            text: "Lorem ipsum dolor sit amet. ".repeat(300),
+            source_lang: Some("la".to_string()),
+            source_romanization: romanization.clone(),
+            target_romanization: romanization,
+            translator: "LibreTranslate",
+        }
+    }
+
+    fn embed_length(message: &OutgoingMessage) -> usize {
+        let fields: usize = embed_fields(message)
+            .iter()
+            .map(|(name, value)| name.chars().count() + value.chars().count())
+            .sum();
+
+        ["title", "description"]
+            .iter()
+            .filter_map(|key| embed_str(message, key))
+            .chain(embed_footer(message))
+            .map(|text| text.chars().count())
+            .sum::<usize>()
+            + fields
+    }
+
+    #[tokio::test]
+    async fn sends_readings_below_the_translation() {
+        let translation = Translation {
+            text: "Good morning".to_string(),
+            source_lang: Some("ja".to_string()),
+            source_romanization: Some("ohayou gozaimasu".to_string()),
+            target_romanization: None,
+            translator: "Google Translate",
+        };
+        let sink = RecordingSink::new();
+
+        send_translation(&sink, "en", &translation, true).await;
+
+        let messages = sink.messages();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            embed_fields(&messages[0]),
+            vec![(
+                "Reading of the original".to_string(),
+                "ohayou gozaimasu".to_string()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn sends_readings_separately_if_they_dont_fit() {
+        // The last part is a full description, leaving no room for two full reading fields
+        let mut translation = long_translation(Some("a".repeat(2000)));
+        translation.text.truncate(4096 * 2 - 10);
+        let sink = RecordingSink::new();
+
+        send_translation(&sink, "en", &translation, true).await;
+
+        let messages = sink.messages();
+        assert_eq!(messages.len(), 3);
+        assert!(embed_fields(&messages[1]).is_empty());
+        assert_eq!(embed_fields(&messages[2]).len(), 2);
+        for message in &messages {
+            assert!(embed_length(message) <= EMBED_TOTAL_LIMIT);
+        }
+    }
+
+    #[tokio::test]
+    async fn splits_long_translations_into_parts() {
+        let translation = long_translation(Some("lorem ipsum".to_string()));
+        let sink = RecordingSink::new();
+
+        send_translation(&sink, "en", &translation, false).await;
+
+        let messages = sink.messages();
+        assert_eq!(messages.len(), 3);
+        assert!(embed_str(&messages[0], "title").is_some());
+        assert!(embed_str(&messages[1], "title").is_none());
+        assert_eq!(
+            embed_footer(&messages[2]),
+            Some("Translated by LibreTranslate | Part 3/3")
+        );
+    }
+}
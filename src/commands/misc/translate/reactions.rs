@@ -0,0 +1,167 @@
+use super::flags::language_for_flag;
+use super::message::message_text;
+use super::{get_translator, send_translation, translate_text};
+use crate::core::{
+    checks::ISADMIN_CHECK,
+    consts::MAIN_COLOR,
+    settings::{get_settings_store, guild_settings},
+    sink::ChannelSink,
+};
+use serenity::{
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    model::{
+        channel::{Message, Reaction, ReactionType},
+        id::MessageId,
+    },
+    prelude::*,
+};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+// How long a message won't be translated into the same language again
+const DEDUPLICATION_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub struct FlagTranslationsContainer;
+impl TypeMapKey for FlagTranslationsContainer {
+    type Value = Arc<Mutex<RecentTranslations>>;
+}
+
+/// Messages recently translated via reactions, so several people reacting
+/// with the same flag don't flood the channel with identical translations
+#[derive(Default)]
+pub struct RecentTranslations {
+    translated: HashMap<(MessageId, &'static str), Instant>,
+}
+
+impl RecentTranslations {
+    /// Marks the message as translated into the given language.
+    /// Returns false if that already happened within the deduplication window.
+    fn claim(&mut self, message_id: MessageId, language: &'static str) -> bool {
+        let now = Instant::now();
+        self.translated
+            .retain(|_, translated_at| now.duration_since(*translated_at) < DEDUPLICATION_WINDOW);
+
+        match self.translated.contains_key(&(message_id, language)) {
+            true => false,
+            false => {
+                self.translated.insert((message_id, language), now);
+                true
+            }
+        }
+    }
+
+    /// Allows the message to be translated again, e.g. after the translation failed
+    fn release(&mut self, message_id: MessageId, language: &'static str) {
+        self.translated.remove(&(message_id, language));
+    }
+}
+
+/// Posts a translation of the reacted to message if the reaction is a country flag
+/// and flag translations are enabled in the guild
+pub async fn translate_flag_reaction(ctx: &Context, reaction: &Reaction) -> CommandResult {
+    let guild_id = match reaction.guild_id {
+        Some(guild_id) => guild_id,
+        None => return Ok(()),
+    };
+
+    let language = match &reaction.emoji {
+        ReactionType::Unicode(emoji) => match language_for_flag(emoji) {
+            Some(language) => language,
+            None => return Ok(()),
+        },
+        _ => return Ok(()),
+    };
+
+    if !guild_settings(ctx, guild_id).await.flag_translations {
+        return Ok(());
+    }
+
+    if reaction.user(ctx).await?.bot {
+        return Ok(());
+    }
+
+    let recent = {
+        let data = ctx.data.read().await;
+        Arc::clone(data.get::<FlagTranslationsContainer>().unwrap())
+    };
+
+    if !recent.lock().await.claim(reaction.message_id, language) {
+        return Ok(());
+    }
+
+    let result = translate_message(ctx, reaction, language).await;
+    if result.is_err() {
+        recent.lock().await.release(reaction.message_id, language);
+    }
+
+    result
+}
+
+async fn translate_message(
+    ctx: &Context,
+    reaction: &Reaction,
+    language: &'static str,
+) -> CommandResult {
+    let message = reaction.message(&ctx.http).await?;
+    let text = message_text(&message)?;
+
+    let translator = get_translator(ctx).await;
+    let translation = translate_text(translator.as_ref(), None, language, &text).await?;
+
+    // Nothing to do if the message already is in the flag's language
+    if translation.source_lang.as_deref() == Some(language) {
+        return Ok(());
+    }
+
+    let sink = ChannelSink::new(&ctx.http, reaction.channel_id);
+    send_translation(&sink, language, &translation, false).await;
+
+    Ok(())
+}
+
+#[command]
+#[only_in(guilds)]
+#[checks(IsAdmin)]
+#[description(
+    "Enables or disables translating messages by reacting to them with a country flag, \
+    e.g. 🇯🇵 to translate them into Japanese. Shows whether it's enabled without an argument."
+)]
+#[usage("[on | off]")]
+#[example("on")]
+pub async fn reactions(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap();
+
+    let currently_enabled = guild_settings(ctx, guild_id).await.flag_translations;
+
+    let enabled = match args.single::<String>().ok().as_deref() {
+        Some("on") => true,
+        Some("off") => false,
+        Some(_) => return Err(CommandError::from("The argument must be either on or off")),
+        None => currently_enabled,
+    };
+
+    if enabled != currently_enabled {
+        let store = get_settings_store(ctx).await;
+        let mut store = store.write().await;
+        store.update(|settings| settings.guild_mut(guild_id).flag_translations = enabled)?;
+    }
+
+    let _ = msg
+        .channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.colour(MAIN_COLOR).title("Flag translations").description(
+                    match enabled {
+                        true => "Reacting to a message with a country flag translates it into that country's language.",
+                        false => "Flag translations are disabled on this server.",
+                    },
+                )
+            })
+        })
+        .await;
+
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use super::languages::find_language;
+use super::message::find_text_to_translate;
+use super::{get_translator, reply_with_error, romanize_text, unknown_language_error, Translator};
+use crate::core::{
+    config::get_config,
+    consts::MAIN_COLOR,
+    sink::{ChannelSink, MessageSink, OutgoingMessage},
+    text::split_text,
+};
+use serenity::{
+    builder::CreateEmbed,
+    framework::standard::{macros::command, Args, CommandResult},
+    model::channel::Message,
+    prelude::Context,
+};
+
+// Discord's limit for embed descriptions
+const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+
+#[command]
+#[aliases("romaji", "transliterate")]
+#[description(
+    "Shows the romanized reading of a text written in a non-latin script, \
+    e.g. romaji for Japanese, pinyin for Chinese or the latin transliteration of Cyrillic. \
+    The language is auto detected, unless it's given with `-l <language>` first. \
+    Instead of a text, you can also reply to a message or give a message link."
+)]
+#[usage("[-l <language>] <text | message link>")]
+#[example("こんにちは、元気ですか？")]
+#[example("-l zh-CN 你好")]
+pub async fn romanize(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let source_lang = match args.current() {
+        Some("-l") | Some("--language") => {
+            let language_arg = args.advance().single::<String>()?;
+
+            match find_language(&language_arg) {
+                Some(language) => Some(language.code),
+                None => {
+                    return Err(unknown_language_error(
+                        get_config(ctx).await.prefix(),
+                        &language_arg,
+                    ))
+                }
+            }
+        }
+        _ => None,
+    };
+
+    let text = find_text_to_translate(ctx, msg, args.rest()).await?;
+    let translator = get_translator(ctx).await;
+    let sink = ChannelSink::new(&ctx.http, msg.channel_id);
+
+    send_romanization(&sink, translator.as_ref(), source_lang, &text).await
+}
+
+/// Romanizes the text and sends the reading, split up into multiple embeds if it doesn't fit into one
+async fn send_romanization(
+    sink: &dyn MessageSink,
+    translator: &dyn Translator,
+    source_lang: Option<&str>,
+    text: &str,
+) -> CommandResult {
+    let romanization = match romanize_text(translator, source_lang, text).await {
+        Ok(Some(romanization)) => romanization,
+        Ok(None) => {
+            let mut embed = CreateEmbed::default();
+            embed.colour(MAIN_COLOR).title("Romanization").description(
+                "There's nothing to romanize, the text is either written in latin script already \
+                or none of the translation services can romanize it.",
+            );
+
+            let _ = sink.send(OutgoingMessage::embed(embed)).await;
+
+            return Ok(());
+        }
+        Err(why) => return reply_with_error(sink, why).await,
+    };
+
+    let parts = split_text(&romanization, EMBED_DESCRIPTION_LIMIT);
+    let part_count = parts.len();
+
+    for (i, part) in parts.into_iter().enumerate() {
+        let mut embed = CreateEmbed::default();
+        embed.colour(MAIN_COLOR).description(part);
+
+        if i == 0 {
+            embed.title("Romanization");
+        }
+        if part_count > 1 {
+            embed.footer(|f| f.text(format!("Part {}/{}", i + 1, part_count)));
+        }
+
+        let _ = sink.send(OutgoingMessage::embed(embed)).await;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::backend::{TranslateError, Translation, TranslatorResult};
+    use super::*;
+    use crate::core::testing::{embed_footer, embed_str, RecordingSink};
+    use serenity::async_trait;
+
+    // Romanizes by repeating the text, or not at all, and can't translate
+    struct FakeTranslator {
+        romanizes: bool,
+    }
+
+    #[async_trait]
+    impl Translator for FakeTranslator {
+        fn name(&self) -> &'static str {
+            "Fake"
+        }
+
+        fn max_text_length(&self) -> usize {
+            5000
+        }
+
+        async fn translate(
+            &self,
+            _source_lang: Option<&str>,
+            _target_lang: &str,
+            _text: &str,
+        ) -> TranslatorResult<Translation> {
+            Err(TranslateError::Rejected("Can't translate".to_string()))
+        }
+
+        async fn romanize(
+            &self,
+            _source_lang: Option<&str>,
+            text: &str,
+        ) -> TranslatorResult<Option<String>> {
+            Ok(Some(text.to_string()).filter(|_| self.romanizes))
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_the_romanization_in_parts() {
+        let text = "konnichiwa. ".repeat(500);
+        let sink = RecordingSink::new();
+
+        send_romanization(
+            &sink,
+            &FakeTranslator { romanizes: true },
+            Some("ja"),
+            &text,
+        )
+        .await
+        .unwrap();
+
+        let messages = sink.messages();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(embed_str(&messages[0], "title"), Some("Romanization"));
+        assert_eq!(embed_footer(&messages[0]), Some("Part 1/2"));
+        assert_eq!(embed_str(&messages[1], "title"), None);
+    }
+
+    #[tokio::test]
+    async fn tells_when_there_is_nothing_to_romanize() {
+        let sink = RecordingSink::new();
+
+        send_romanization(&sink, &FakeTranslator { romanizes: false }, None, "hello")
+            .await
+            .unwrap();
+
+        let messages = sink.messages();
+        assert_eq!(messages.len(), 1);
+        assert!(embed_str(&messages[0], "description")
+            .unwrap()
+            .starts_with("There's nothing to romanize"));
+    }
+}
@@ -0,0 +1,170 @@
+use image::{DynamicImage, ImageOutputFormat, RgbImage};
+use lazy_static::lazy_static;
+use plotters::{prelude::*, style::register_font};
+use std::error::Error;
+
+const CHART_WIDTH: u32 = 800;
+const CHART_HEIGHT: u32 = 400;
+
+const TEMP_MAX_COLOR: RGBColor = RGBColor(0xe5, 0x39, 0x35);
+const TEMP_MIN_COLOR: RGBColor = RGBColor(0xfb, 0x8c, 0x00);
+const PRECIPITATION_COLOR: RGBColor = RGBColor(0x1e, 0x88, 0xe5);
+
+// Plotters has no fonts of its own, the labels are drawn with this one
+static FONT: &[u8] = include_bytes!("../../../../assets/fonts/DejaVuSans.ttf");
+
+lazy_static! {
+    static ref FONT_REGISTERED: bool = register_font("sans-serif", FontStyle::Normal, FONT).is_ok();
+}
+
+/// A single day of the forecast, as drawn into the chart
+pub struct ChartDay {
+    pub label: String,
+    pub temp_min: f64,
+    pub temp_max: f64,
+    pub precipitation: f64,
+}
+
+/// Renders a line chart of the daily min/max temperatures and precipitation amounts
+/// for the given forecast days. Returns a PNG image.
+pub fn render_forecast_chart(days: &[ChartDay]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    if days.len() < 2 {
+        return Err("Not enough forecast data to draw a chart".into());
+    }
+
+    if !*FONT_REGISTERED {
+        return Err("The chart font couldn't be loaded".into());
+    }
+
+    let mut buffer = vec![0u8; (CHART_WIDTH * CHART_HEIGHT * 3) as usize];
+
+    {
+        let root = BitMapBackend::with_buffer(&mut buffer, (CHART_WIDTH, CHART_HEIGHT))
+            .into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let last_day = days.len() as i32 - 1;
+
+        // Leave some room above and below the temperature lines
+        let temp_low = days
+            .iter()
+            .map(|d| d.temp_min)
+            .fold(f64::INFINITY, f64::min)
+            - 2.0;
+        let temp_high = days
+            .iter()
+            .map(|d| d.temp_max)
+            .fold(f64::NEG_INFINITY, f64::max)
+            + 2.0;
+        let precipitation_high = days.iter().map(|d| d.precipitation).fold(1.0, f64::max) * 1.2;
+
+        let mut chart = ChartBuilder::on(&root)
+            .margin(15)
+            .x_label_area_size(30)
+            .y_label_area_size(45)
+            .right_y_label_area_size(45)
+            .build_cartesian_2d(0..last_day, temp_low..temp_high)?
+            .set_secondary_coord(0..last_day, 0.0..precipitation_high);
+
+        chart
+            .configure_mesh()
+            .x_labels(days.len())
+            .x_label_formatter(&|i| {
+                days.get(*i as usize)
+                    .map(|d| d.label.clone())
+                    .unwrap_or_default()
+            })
+            .y_desc("Temperature (°C)")
+            .y_label_formatter(&|t| format!("{:.0}", t))
+            .draw()?;
+
+        chart
+            .configure_secondary_axes()
+            .y_desc("Precipitation (mm)")
+            .y_label_formatter(&|p| format!("{:.1}", p))
+            .draw()?;
+
+        chart
+            .draw_secondary_series(LineSeries::new(
+                days.iter()
+                    .enumerate()
+                    .map(|(i, d)| (i as i32, d.precipitation)),
+                PRECIPITATION_COLOR.stroke_width(2),
+            ))?
+            .label("Precipitation")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], PRECIPITATION_COLOR));
+
+        chart
+            .draw_series(LineSeries::new(
+                days.iter().enumerate().map(|(i, d)| (i as i32, d.temp_max)),
+                TEMP_MAX_COLOR.stroke_width(2),
+            ))?
+            .label("Max temp")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], TEMP_MAX_COLOR));
+
+        chart
+            .draw_series(LineSeries::new(
+                days.iter().enumerate().map(|(i, d)| (i as i32, d.temp_min)),
+                TEMP_MIN_COLOR.stroke_width(2),
+            ))?
+            .label("Min temp")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], TEMP_MIN_COLOR));
+
+        chart
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        root.present()?;
+    }
+
+    let image = RgbImage::from_raw(CHART_WIDTH, CHART_HEIGHT, buffer)
+        .ok_or("Chart buffer has an unexpected size")?;
+
+    let mut png_bytes = Vec::new();
+    DynamicImage::ImageRgb8(image).write_to(&mut png_bytes, ImageOutputFormat::Png)?;
+
+    Ok(png_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_a_png_with_labels() {
+        let days: Vec<ChartDay> = (0..7)
+            .map(|i| ChartDay {
+                label: format!("{} Jul", 12 + i),
+                temp_min: 14.0 + i as f64,
+                temp_max: 24.0 + i as f64,
+                precipitation: (i % 3) as f64,
+            })
+            .collect();
+
+        let png = render_forecast_chart(&days).unwrap();
+
+        let image = image::load_from_memory(&png).unwrap().to_rgb8();
+        assert_eq!(image.dimensions(), (CHART_WIDTH, CHART_HEIGHT));
+
+        // The day labels are drawn below the axis and its ticks, and nothing else is there
+        let label_pixels = (CHART_HEIGHT - 35..CHART_HEIGHT - 15)
+            .flat_map(|y| (60..CHART_WIDTH - 60).map(move |x| (x, y)))
+            .filter(|&(x, y)| image.get_pixel(x, y).0 != [255, 255, 255])
+            .count();
+        assert!(label_pixels > 200, "Only {} pixels of labels", label_pixels);
+    }
+
+    #[test]
+    fn needs_at_least_two_days() {
+        let days = vec![ChartDay {
+            label: "12 Jul".to_string(),
+            temp_min: 14.0,
+            temp_max: 24.0,
+            precipitation: 0.0,
+        }];
+
+        assert!(render_forecast_chart(&days).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use super::provider::WeatherProvider;
+use crate::core::http::{HttpClient, Service};
+use image::{imageops, ImageOutputFormat, Rgba, RgbaImage};
+use serenity::futures::future::try_join_all;
+use std::{error::Error, f64::consts::PI};
+
+pub const MAP_ZOOM: i32 = 7;
+
+const TILE_SIZE: u32 = 256;
+
+// Number of tiles to stitch around the center tile in each direction (1 => 3x3 tiles)
+const TILE_RADIUS: i32 = 1;
+
+const MARKER_RADIUS: i32 = 7;
+const MARKER_COLOR: Rgba<u8> = Rgba([0xe5, 0x39, 0x35, 0xff]);
+const MARKER_BORDER_COLOR: Rgba<u8> = Rgba([0xff, 0xff, 0xff, 0xff]);
+
+/// Renders a map around the given location, made up of base tiles from the tile server
+/// (`{z}`, `{x}` and `{y}` in its url standing for the tile) with the weather provider's map layer
+/// drawn on top (if it has one), and a marker at the location itself. Returns a PNG image.
+pub async fn render_weather_map(
+    client: &HttpClient,
+    provider: &dyn WeatherProvider,
+    tile_url: &str,
+    lat: f64,
+    lon: f64,
+) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+    let (tile_x, tile_y) = get_map_coords_from_lat_lon(lat, lon, MAP_ZOOM);
+    let (center_x, center_y) = (tile_x.floor() as i32, tile_y.floor() as i32);
+
+    let tiles_per_side = (TILE_RADIUS * 2 + 1) as u32;
+    let mut map = RgbaImage::new(tiles_per_side * TILE_SIZE, tiles_per_side * TILE_SIZE);
+
+    // Collect every tile position making up the map, together with their pixel offset
+    let tile_positions: Vec<(i32, i32, u32, u32)> = (-TILE_RADIUS..=TILE_RADIUS)
+        .flat_map(|dy| (-TILE_RADIUS..=TILE_RADIUS).map(move |dx| (dx, dy)))
+        .filter_map(|(dx, dy)| {
+            let (x, y) = wrap_tile_coords(center_x + dx, center_y + dy, MAP_ZOOM)?;
+            let offset_x = ((dx + TILE_RADIUS) as u32) * TILE_SIZE;
+            let offset_y = ((dy + TILE_RADIUS) as u32) * TILE_SIZE;
+
+            Some((x, y, offset_x, offset_y))
+        })
+        .collect();
+
+    // Fetch base and weather layer tiles for all positions at once
+    let tiles = try_join_all(
+        tile_positions
+            .iter()
+            .map(|&(x, y, offset_x, offset_y)| async move {
+                let base = fetch_tile(client, &get_base_map_url(tile_url, (x, y))).await?;
+                let weather = match provider.map_tile_url(MAP_ZOOM, x, y) {
+                    Some(url) => Some(fetch_tile(client, &url).await?),
+                    None => None,
+                };
+
+                Ok::<_, Box<dyn Error + Send + Sync>>((base, weather, offset_x, offset_y))
+            }),
+    )
+    .await?;
+
+    for (base, weather, offset_x, offset_y) in tiles {
+        imageops::overlay(&mut map, &base, offset_x, offset_y);
+
+        if let Some(weather) = weather {
+            imageops::overlay(&mut map, &weather, offset_x, offset_y);
+        }
+    }
+
+    // Position of the location inside the stitched map, in pixels
+    let marker_x = ((tile_x - (center_x - TILE_RADIUS) as f64) * TILE_SIZE as f64) as i32;
+    let marker_y = ((tile_y - (center_y - TILE_RADIUS) as f64) * TILE_SIZE as f64) as i32;
+
+    draw_marker(&mut map, marker_x, marker_y);
+
+    let mut png_bytes = Vec::new();
+    image::DynamicImage::ImageRgba8(map).write_to(&mut png_bytes, ImageOutputFormat::Png)?;
+
+    Ok(png_bytes)
+}
+
+/// Converts a latitude/longitude pair into fractional slippy map tile coordinates,
+/// as described on https://wiki.openstreetmap.org/wiki/Slippy_map_tilenames.
+/// The integer part is the tile number, the fractional part the position inside the tile.
+pub fn get_map_coords_from_lat_lon(lat: f64, lon: f64, zoom: i32) -> (f64, f64) {
+    // Web mercator is only defined up to about 85.0511° north and south
+    let lat_rad = lat.clamp(-85.0511, 85.0511).to_radians();
+    let n = 2.0_f64.powi(zoom);
+
+    let x = n * ((lon + 180.0) / 360.0);
+    let y = n * (1.0 - (lat_rad.tan() + 1.0 / lat_rad.cos()).ln() / PI) / 2.0;
+
+    (x, y)
+}
+
+// Tiles wrap around horizontally at the antimeridian, but there's nothing above or below the poles
+fn wrap_tile_coords(x: i32, y: i32, zoom: i32) -> Option<(i32, i32)> {
+    let n = 2_i32.pow(zoom as u32);
+
+    match y {
+        y if y < 0 || y >= n => None,
+        y => Some((x.rem_euclid(n), y)),
+    }
+}
+
+// The OpenStreetMap tile usage policy requires an identifying user agent, which the client sends
+async fn fetch_tile(
+    client: &HttpClient,
+    url: &str,
+) -> Result<RgbaImage, Box<dyn Error + Send + Sync>> {
+    let bytes = client
+        .send(Service::MapTiles, client.get(url))
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    Ok(image::load_from_memory(&bytes)?.to_rgba8())
+}
+
+fn get_base_map_url(tile_url: &str, coords: (i32, i32)) -> String {
+    tile_url
+        .replace("{z}", &MAP_ZOOM.to_string())
+        .replace("{x}", &coords.0.to_string())
+        .replace("{y}", &coords.1.to_string())
+}
+
+fn draw_marker(map: &mut RgbaImage, center_x: i32, center_y: i32) {
+    let border_radius = MARKER_RADIUS + 2;
+
+    for dy in -border_radius..=border_radius {
+        for dx in -border_radius..=border_radius {
+            let (x, y) = (center_x + dx, center_y + dy);
+
+            if x < 0 || y < 0 || x >= map.width() as i32 || y >= map.height() as i32 {
+                continue;
+            }
+
+            let distance_sq = dx * dx + dy * dy;
+
+            if distance_sq <= MARKER_RADIUS * MARKER_RADIUS {
+                map.put_pixel(x as u32, y as u32, MARKER_COLOR);
+            } else if distance_sq <= border_radius * border_radius {
+                map.put_pixel(x as u32, y as u32, MARKER_BORDER_COLOR);
+            }
+        }
+    }
+}
@@ -0,0 +1,485 @@
+mod chart;
+mod map;
+pub mod provider;
+pub mod subscription;
+
+use self::chart::{render_forecast_chart, ChartDay};
+use self::map::render_weather_map;
+use self::provider::{
+    Forecast, Location, ProviderResult, WeatherError, WeatherProvider, WeatherProviderContainer,
+};
+use self::subscription::{SUBSCRIBE_COMMAND, SUBSCRIPTIONS_COMMAND, UNSUBSCRIBE_COMMAND};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use log::warn;
+use serenity::{
+    builder::CreateEmbed,
+    framework::standard::{macros::command, Args, CommandResult},
+    model::channel::Message,
+    prelude::Context,
+};
+use std::sync::Arc;
+
+use crate::core::{
+    config::get_config,
+    consts::MAIN_COLOR,
+    http::{get_http_client, HttpClient},
+    sink::{ChannelSink, MessageSink, OutgoingMessage},
+};
+
+#[command]
+#[description("Retrieves the weather forecast at the given location")]
+#[example("Berlin")]
+#[example("Sri Lanka")]
+#[example("New York")]
+#[sub_commands(subscribe, unsubscribe, subscriptions)]
+pub async fn weather(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let provider = get_provider(ctx).await;
+    let client = get_http_client(ctx).await;
+    let config = get_config(ctx).await;
+    let sink = ChannelSink::new(&ctx.http, msg.channel_id);
+
+    // Get coordinates for given location
+    let search_arg = args.single::<String>()?;
+
+    send_weather(
+        &sink,
+        &client,
+        provider.as_ref(),
+        &config.weather.map_tile_url,
+        &search_arg,
+    )
+    .await
+}
+
+/// Sends the current weather and the forecast at the location, each with a rendered image
+async fn send_weather(
+    sink: &dyn MessageSink,
+    client: &HttpClient,
+    provider: &dyn WeatherProvider,
+    map_tile_url: &str,
+    search_arg: &str,
+) -> CommandResult {
+    let (location, weather) = match query_forecast(provider, search_arg).await {
+        Ok(result) => result,
+        Err(why) => return reply_with_error(sink, why).await,
+    };
+
+    // Render the map around the location. If that fails, still send the weather without it
+    let map_image = match render_weather_map(
+        client,
+        provider,
+        map_tile_url,
+        location.lat,
+        location.lon,
+    )
+    .await
+    {
+        Ok(image) => Some(image),
+        Err(why) => {
+            warn!("Could not render weather map: {:?}", why);
+            None
+        }
+    };
+
+    let mut current_embed = CreateEmbed::default();
+    build_current_embed(&mut current_embed, search_arg, &weather, provider.name());
+
+    let current_message = match map_image {
+        Some(image) => {
+            current_embed.image("attachment://map.png");
+            OutgoingMessage::embed(current_embed).file("map.png", image)
+        }
+        None => OutgoingMessage::embed(current_embed),
+    };
+    let _ = sink.send(current_message).await;
+
+    // Render the forecast chart, again leaving it out if it fails
+    let chart_days: Vec<ChartDay> = weather
+        .daily
+        .iter()
+        .map(|day_weather| ChartDay {
+            label: format_timestamp(day_weather.dt, weather.timezone_offset, "%e %b"),
+            temp_min: day_weather.temp_min,
+            temp_max: day_weather.temp_max,
+            precipitation: day_weather.precipitation,
+        })
+        .collect();
+
+    let chart_image = match render_forecast_chart(&chart_days) {
+        Ok(image) => Some(image),
+        Err(why) => {
+            warn!("Could not render forecast chart: {:?}", why);
+            None
+        }
+    };
+
+    let mut forecast_embed = CreateEmbed::default();
+    build_forecast_embed(&mut forecast_embed, search_arg, &weather, provider.name());
+
+    let forecast_message = match chart_image {
+        Some(image) => {
+            forecast_embed.image("attachment://forecast.png");
+            OutgoingMessage::embed(forecast_embed).file("forecast.png", image)
+        }
+        None => OutgoingMessage::embed(forecast_embed),
+    };
+    let _ = sink.send(forecast_message).await;
+
+    Ok(())
+}
+
+fn build_current_embed<'a>(
+    e: &'a mut CreateEmbed,
+    location: &str,
+    weather: &Forecast,
+    provider_name: &str,
+) -> &'a mut CreateEmbed {
+    if let Some(image_url) = get_weather_image_url(&weather.current.condition.icon) {
+        e.thumbnail(image_url);
+    }
+
+    e.colour(MAIN_COLOR)
+        .title(format!("Weather in {}", location))
+        .description(format!(
+            "{} **{}** \n\
+            **Temp**: {:.0}°C (Feels like {:.0}°C)",
+            get_weather_emoji(&weather.current.condition.icon),
+            uppercase_first(&weather.current.condition.description),
+            &weather.current.temp,
+            &weather.current.feels_like
+        ))
+        .fields(vec![
+            (
+                "Weather",
+                format!(
+                    "**Clouds**: {}% \n\
+                    **Humidity**: {}% \n\
+                    **Pressure**: {} hpa",
+                    &weather.current.clouds, &weather.current.humidity, &weather.current.pressure
+                ),
+                true,
+            ),
+            (
+                "Wind",
+                format!(
+                    "**Speed**: {}\n\
+                    **Direction**: {}° ({})",
+                    &weather.current.wind_speed,
+                    weather.current.wind_deg,
+                    format_direction(weather.current.wind_deg)
+                ),
+                true,
+            ),
+            (
+                "Location",
+                format!(
+                    "**Sunrise**: {}\n\
+                    **Sunset**: {}\n\
+                    **Local Time**: {}",
+                    format_timestamp(weather.current.sunrise, weather.timezone_offset, "%H:%M"),
+                    format_timestamp(weather.current.sunset, weather.timezone_offset, "%H:%M"),
+                    format_timestamp(
+                        weather.current.dt,
+                        weather.timezone_offset,
+                        "%H:%M, %e %b %Y"
+                    ),
+                ),
+                false,
+            ),
+        ])
+        .footer(|f| f.text(format!("Weather data by {}", provider_name)))
+}
+
+fn build_forecast_embed<'a>(
+    e: &'a mut CreateEmbed,
+    location: &str,
+    weather: &Forecast,
+    provider_name: &str,
+) -> &'a mut CreateEmbed {
+    e.colour(MAIN_COLOR)
+        .title(format!("Forecast for {}", location))
+        .footer(|f| f.text(format!("Weather data by {}", provider_name)));
+
+    for day_weather in weather.daily.iter().skip(1) {
+        e.field(
+            format_timestamp(day_weather.dt, weather.timezone_offset, "%e %b %Y"),
+            format!(
+                "{} **{}** \n\
+                **Temp**: {:.0}°C\n\
+                **Humidity**: {}%",
+                get_weather_emoji(&day_weather.condition.icon),
+                uppercase_first(&day_weather.condition.description),
+                &day_weather.temp_day,
+                &day_weather.humidity
+            ),
+            true,
+        );
+    }
+
+    e
+}
+
+async fn query_forecast(
+    provider: &dyn WeatherProvider,
+    query: &str,
+) -> ProviderResult<(Location, Forecast)> {
+    let location = provider.find_location(query).await?;
+    let forecast = provider.forecast(location.lat, location.lon).await?;
+
+    Ok((location, forecast))
+}
+
+// Tells the user about errors they can do something about,
+// everything else is handed to the after hook to be reported
+async fn reply_with_error(sink: &dyn MessageSink, why: WeatherError) -> CommandResult {
+    if !why.is_user_facing() {
+        return Err(why.into());
+    }
+
+    let mut embed = CreateEmbed::default();
+    embed
+        .colour(MAIN_COLOR)
+        .title("No weather for you :(")
+        .description(why.to_string());
+
+    let _ = sink.send(OutgoingMessage::embed(embed)).await;
+
+    Ok(())
+}
+
+async fn get_provider(ctx: &Context) -> Arc<dyn WeatherProvider> {
+    let data = ctx.data.read().await;
+
+    Arc::clone(data.get::<WeatherProviderContainer>().unwrap())
+}
+
+// Providers without an icon for the condition leave the code empty, which has no image
+fn get_weather_image_url(code: &str) -> Option<String> {
+    match code.is_empty() {
+        true => None,
+        false => Some(format!("http://openweathermap.org/img/wn/{}@2x.png", code)),
+    }
+}
+
+fn get_weather_emoji(code: &str) -> String {
+    match code {
+        "01d" => "☀️",
+        "01n" => "🌕",
+        "02d" | "02n" => "⛅",
+        "03d" | "03n" => "☁️",
+        "04d" | "04n" => "☁️",
+        "09d" | "09n" => "🌧️",
+        "10d" | "10n" => "🌧️",
+        "11d" | "11n" => "🌩️",
+        "13d" | "13n" => "❄️",
+        "50d" | "50n" => "🌫️",
+        _ => "❔",
+    }
+    .to_string()
+}
+
+fn uppercase_first(s: &str) -> String {
+    let mut chars = s.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+// "%H:%M, %e %b %Y"
+fn format_timestamp(timestamp: i64, offset: i32, format: &str) -> String {
+    let date_time = DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp(timestamp, 0), Utc);
+    let local_time = date_time.with_timezone(&FixedOffset::east(offset));
+
+    local_time.format(format).to_string()
+}
+
+fn format_direction(degrees: i32) -> String {
+    match degrees {
+        x if x <= 45 || x > 315 => "North",
+        x if x <= 135 => "East",
+        x if x <= 225 => "South",
+        x if x <= 315 => "West",
+        _ => "",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::provider::{Condition, MockProvider};
+    use super::*;
+    use crate::core::testing::{
+        embed_fields, embed_footer, embed_str, embed_value, fixture_bytes, MockResponse,
+        MockServer, RecordingSink,
+    };
+
+    async fn mock_forecast() -> Forecast {
+        let (location, forecast) = query_forecast(&MockProvider, "Berlin").await.unwrap();
+        assert_eq!(location.name, "Berlin");
+
+        forecast
+    }
+
+    #[tokio::test]
+    async fn formats_the_current_weather() {
+        let forecast = mock_forecast().await;
+        let mut embed = CreateEmbed::default();
+
+        build_current_embed(&mut embed, "Berlin", &forecast, MockProvider.name());
+        let message = OutgoingMessage::embed(embed);
+
+        assert_eq!(embed_str(&message, "title"), Some("Weather in Berlin"));
+        assert_eq!(
+            embed_value(&message, "thumbnail").unwrap()["url"],
+            "http://openweathermap.org/img/wn/03d@2x.png"
+        );
+        assert_eq!(
+            embed_str(&message, "description"),
+            Some("☁️ **Scattered clouds** \n**Temp**: 19°C (Feels like 19°C)")
+        );
+        assert_eq!(embed_footer(&message), Some("Weather data by Mock"));
+
+        let fields = embed_fields(&message);
+        let names: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["Weather", "Wind", "Location"]);
+        assert!(fields[1].1.ends_with("**Direction**: 250° (West)"));
+        // The mock's sunrise is at 5:00 UTC, shown in the location's UTC+2
+        assert!(fields[2]
+            .1
+            .starts_with("**Sunrise**: 07:00\n**Sunset**: 21:00"));
+    }
+
+    #[tokio::test]
+    async fn formats_the_forecast_without_today() {
+        let forecast = mock_forecast().await;
+        let mut embed = CreateEmbed::default();
+
+        build_forecast_embed(&mut embed, "Berlin", &forecast, MockProvider.name());
+        let message = OutgoingMessage::embed(embed);
+
+        assert_eq!(embed_str(&message, "title"), Some("Forecast for Berlin"));
+        assert_eq!(embed_footer(&message), Some("Weather data by Mock"));
+
+        let fields = embed_fields(&message);
+        assert_eq!(fields.len(), 7);
+        assert_eq!(
+            fields[0].1,
+            "☀️ **Clear sky** \n**Temp**: 19°C\n**Humidity**: 65%"
+        );
+    }
+
+    #[tokio::test]
+    async fn leaves_out_the_thumbnail_of_unknown_conditions() {
+        let mut forecast = mock_forecast().await;
+        forecast.current.condition = Condition::unknown();
+        let mut embed = CreateEmbed::default();
+
+        build_current_embed(&mut embed, "Berlin", &forecast, MockProvider.name());
+        let message = OutgoingMessage::embed(embed);
+
+        assert!(embed_value(&message, "thumbnail").is_none());
+        assert!(embed_str(&message, "description")
+            .unwrap()
+            .starts_with("❔ **Unknown**"));
+    }
+
+    #[test]
+    fn uppercases_multibyte_first_letters() {
+        assert_eq!(uppercase_first("éclaircies"), "Éclaircies");
+        assert_eq!(uppercase_first("ñublado"), "Ñublado");
+        // Some letters turn into several when uppercased
+        assert_eq!(uppercase_first("ßchnee"), "SSchnee");
+        assert_eq!(uppercase_first("小雨"), "小雨");
+        assert_eq!(uppercase_first(""), "");
+    }
+
+    #[tokio::test]
+    async fn sends_the_weather_with_map_and_chart() {
+        let server = MockServer::start();
+        server.mock(
+            "GET",
+            "/tiles/*",
+            MockResponse::new(200, fixture_bytes("weather/tile.png")),
+        );
+        let tile_url = format!("{}/tiles/{{z}}/{{x}}/{{y}}.png", server.url());
+        let sink = RecordingSink::new();
+
+        send_weather(
+            &sink,
+            &HttpClient::new(),
+            &MockProvider,
+            &tile_url,
+            "Berlin",
+        )
+        .await
+        .unwrap();
+
+        let messages = sink.messages();
+        assert_eq!(messages.len(), 2);
+
+        let current = &messages[0];
+        assert_eq!(embed_str(current, "title"), Some("Weather in Berlin"));
+        assert_eq!(
+            embed_value(current, "image").unwrap()["url"],
+            "attachment://map.png"
+        );
+        assert_eq!(current.files[0].0, "map.png");
+        // 3x3 tiles around Berlin at zoom level 7
+        let requests = server.requests();
+        assert_eq!(requests.len(), 9);
+        assert!(requests
+            .iter()
+            .any(|request| request.path == "/tiles/7/68/41.png"));
+
+        let forecast = &messages[1];
+        assert_eq!(embed_str(forecast, "title"), Some("Forecast for Berlin"));
+        assert_eq!(forecast.files[0].0, "forecast.png");
+        assert_eq!(embed_fields(forecast).len(), 7);
+    }
+
+    #[tokio::test]
+    async fn sends_the_weather_without_map_if_the_tiles_are_missing() {
+        let server = MockServer::start();
+        let tile_url = format!("{}/tiles/{{z}}/{{x}}/{{y}}.png", server.url());
+        let sink = RecordingSink::new();
+
+        send_weather(
+            &sink,
+            &HttpClient::new(),
+            &MockProvider,
+            &tile_url,
+            "Berlin",
+        )
+        .await
+        .unwrap();
+
+        let messages = sink.messages();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(embed_str(&messages[0], "title"), Some("Weather in Berlin"));
+        assert!(embed_value(&messages[0], "image").is_none());
+        assert!(messages[0].files.is_empty());
+    }
+
+    #[tokio::test]
+    async fn replies_with_user_facing_errors() {
+        let sink = RecordingSink::new();
+
+        reply_with_error(
+            &sink,
+            WeatherError::LocationNotFound("Atlantis".to_string()),
+        )
+        .await
+        .unwrap();
+        let result = reply_with_error(&sink, WeatherError::InvalidApiKey).await;
+
+        let messages = sink.messages();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            embed_str(&messages[0], "description"),
+            Some("I couldn't find a location called 'Atlantis'.")
+        );
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,96 @@
+use super::{Forecast, Location, ProviderResult, WeatherProvider};
+use serenity::{async_trait, prelude::Mutex};
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+/// Wraps a provider and keeps its responses around for a while,
+/// so repeated lookups of the same place don't use up the api quota.
+pub struct CachedProvider {
+    inner: Box<dyn WeatherProvider>,
+    locations: TtlCache<String, Location>,
+    forecasts: TtlCache<(i64, i64), Forecast>,
+}
+
+impl CachedProvider {
+    pub fn new(inner: Box<dyn WeatherProvider>, ttl: Duration) -> Self {
+        CachedProvider {
+            inner,
+            locations: TtlCache::new(ttl),
+            forecasts: TtlCache::new(ttl),
+        }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for CachedProvider {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    async fn find_location(&self, query: &str) -> ProviderResult<Location> {
+        let key = query.trim().to_lowercase();
+
+        if let Some(location) = self.locations.get(&key).await {
+            return Ok(location);
+        }
+
+        let location = self.inner.find_location(query).await?;
+        self.locations.insert(key, location.clone()).await;
+
+        Ok(location)
+    }
+
+    async fn forecast(&self, lat: f64, lon: f64) -> ProviderResult<Forecast> {
+        // Round to roughly one kilometer, so nearby lookups share the same forecast
+        let key = ((lat * 100.0).round() as i64, (lon * 100.0).round() as i64);
+
+        if let Some(forecast) = self.forecasts.get(&key).await {
+            return Ok(forecast);
+        }
+
+        let forecast = self.inner.forecast(lat, lon).await?;
+        self.forecasts.insert(key, forecast.clone()).await;
+
+        Ok(forecast)
+    }
+
+    fn map_tile_url(&self, zoom: i32, x: i32, y: i32) -> Option<String> {
+        self.inner.map_tile_url(zoom, x, y)
+    }
+}
+
+struct TtlCache<K, V> {
+    ttl: Duration,
+    entries: Mutex<HashMap<K, (Instant, V)>>,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+    fn new(ttl: Duration) -> Self {
+        TtlCache {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn get(&self, key: &K) -> Option<V> {
+        let entries = self.entries.lock().await;
+
+        match entries.get(key) {
+            Some((inserted, value)) if inserted.elapsed() < self.ttl => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    async fn insert(&self, key: K, value: V) {
+        let mut entries = self.entries.lock().await;
+
+        // Drop everything that expired in the meantime, so the cache doesn't grow forever
+        let ttl = self.ttl;
+        entries.retain(|_, (inserted, _)| inserted.elapsed() < ttl);
+
+        entries.insert(key, (Instant::now(), value));
+    }
+}
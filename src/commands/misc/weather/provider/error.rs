@@ -0,0 +1,198 @@
+use reqwest::{header::RETRY_AFTER, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use std::{error::Error, fmt};
+
+#[derive(Debug)]
+pub enum WeatherError {
+    LocationNotFound(String),
+    InvalidApiKey,
+    // Seconds until requests are allowed again, if the provider told us
+    RateLimited(Option<u64>),
+    Unavailable(StatusCode),
+    InvalidResponse(serde_json::Error),
+    Request(reqwest::Error),
+}
+
+impl WeatherError {
+    /// Whether the error was caused by the user's input or a temporary condition,
+    /// rather than something the bot owner needs to look into
+    pub fn is_user_facing(&self) -> bool {
+        matches!(
+            self,
+            WeatherError::LocationNotFound(_) | WeatherError::RateLimited(_)
+        )
+    }
+}
+
+impl fmt::Display for WeatherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeatherError::LocationNotFound(query) => {
+                write!(f, "I couldn't find a location called '{}'.", query)
+            }
+            WeatherError::InvalidApiKey => {
+                write!(
+                    f,
+                    "The weather api key is invalid or hasn't been activated yet."
+                )
+            }
+            WeatherError::RateLimited(Some(seconds)) => write!(
+                f,
+                "I've asked for the weather too often, please try again in {} seconds.",
+                seconds
+            ),
+            WeatherError::RateLimited(None) => write!(
+                f,
+                "I've asked for the weather too often, please try again in a minute."
+            ),
+            WeatherError::Unavailable(status) => {
+                write!(
+                    f,
+                    "The weather service is unavailable right now ({}).",
+                    status
+                )
+            }
+            WeatherError::InvalidResponse(why) => {
+                write!(
+                    f,
+                    "The weather service sent an unexpected response: {}",
+                    why
+                )
+            }
+            WeatherError::Request(why) => {
+                write!(f, "Couldn't reach the weather service: {}", why)
+            }
+        }
+    }
+}
+
+impl Error for WeatherError {}
+
+impl From<reqwest::Error> for WeatherError {
+    fn from(why: reqwest::Error) -> Self {
+        WeatherError::Request(why)
+    }
+}
+
+/// Checks the status of a provider response and parses its body.
+/// `query` is the searched location, reported back when the provider couldn't find it.
+pub async fn parse_response<T: DeserializeOwned>(
+    response: Response,
+    query: &str,
+) -> Result<T, WeatherError> {
+    let status = response.status();
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok());
+
+    let body = response.text().await?;
+
+    parse_body(status, retry_after, &body, query)
+}
+
+/// Maps a response's status and body to either the parsed data or the matching error
+pub fn parse_body<T: DeserializeOwned>(
+    status: StatusCode,
+    retry_after: Option<u64>,
+    body: &str,
+    query: &str,
+) -> Result<T, WeatherError> {
+    match status {
+        StatusCode::UNAUTHORIZED => Err(WeatherError::InvalidApiKey),
+        StatusCode::NOT_FOUND => Err(WeatherError::LocationNotFound(query.to_string())),
+        StatusCode::TOO_MANY_REQUESTS => Err(WeatherError::RateLimited(retry_after)),
+        status if !status.is_success() => Err(WeatherError::Unavailable(status)),
+        _ => serde_json::from_str(body).map_err(WeatherError::InvalidResponse),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        http::HttpClient,
+        testing::{fixture, MockResponse, MockServer},
+    };
+    use serde_json::Value;
+
+    fn parse_error(status: u16, retry_after: Option<u64>, path: &str) -> WeatherError {
+        let status = StatusCode::from_u16(status).unwrap();
+
+        parse_body::<Value>(status, retry_after, &fixture(path), "Atlantis").unwrap_err()
+    }
+
+    #[test]
+    fn maps_an_invalid_api_key() {
+        let error = parse_error(401, None, "weather/invalid_key.json");
+
+        assert!(matches!(error, WeatherError::InvalidApiKey));
+        assert!(!error.is_user_facing());
+    }
+
+    #[test]
+    fn maps_an_unknown_location() {
+        let error = parse_error(404, None, "weather/not_found.json");
+
+        assert!(matches!(&error, WeatherError::LocationNotFound(query) if query == "Atlantis"));
+        assert!(error.is_user_facing());
+        assert_eq!(
+            error.to_string(),
+            "I couldn't find a location called 'Atlantis'."
+        );
+    }
+
+    #[test]
+    fn maps_rate_limits_with_and_without_delay() {
+        let error = parse_error(429, Some(30), "weather/rate_limited.json");
+        assert!(matches!(error, WeatherError::RateLimited(Some(30))));
+        assert!(error.is_user_facing());
+        assert_eq!(
+            error.to_string(),
+            "I've asked for the weather too often, please try again in 30 seconds."
+        );
+
+        let error = parse_error(429, None, "weather/rate_limited.json");
+        assert_eq!(
+            error.to_string(),
+            "I've asked for the weather too often, please try again in a minute."
+        );
+    }
+
+    #[test]
+    fn maps_other_failures() {
+        let error = parse_body::<Value>(StatusCode::BAD_GATEWAY, None, "", "Atlantis").unwrap_err();
+        assert!(matches!(
+            error,
+            WeatherError::Unavailable(StatusCode::BAD_GATEWAY)
+        ));
+
+        let error = parse_body::<Value>(StatusCode::OK, None, "<html>", "Atlantis").unwrap_err();
+        assert!(matches!(error, WeatherError::InvalidResponse(_)));
+        assert!(!error.is_user_facing());
+    }
+
+    #[tokio::test]
+    async fn reads_the_retry_after_header() {
+        let server = MockServer::start();
+        server.mock(
+            "GET",
+            "/weather",
+            MockResponse::new(429, fixture("weather/rate_limited.json"))
+                .header("Retry-After", "30"),
+        );
+
+        let client = HttpClient::new();
+        let response = client
+            .get(format!("{}/weather", server.url()))
+            .send()
+            .await
+            .unwrap();
+        let error = parse_response::<Value>(response, "Atlantis")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, WeatherError::RateLimited(Some(30))));
+    }
+}
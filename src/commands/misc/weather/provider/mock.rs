@@ -0,0 +1,75 @@
+use super::{
+    Condition, CurrentWeather, DailyWeather, Forecast, Location, ProviderResult, WeatherProvider,
+};
+use chrono::Utc;
+use serenity::async_trait;
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+/// Offline provider returning made up but plausible weather for any location,
+/// for trying out the weather embeds without an internet connection or api key
+pub struct MockProvider;
+
+#[async_trait]
+impl WeatherProvider for MockProvider {
+    fn name(&self) -> &'static str {
+        "Mock"
+    }
+
+    async fn find_location(&self, query: &str) -> ProviderResult<Location> {
+        Ok(Location {
+            name: query.to_string(),
+            lat: 52.52,
+            lon: 13.405,
+        })
+    }
+
+    async fn forecast(&self, _lat: f64, _lon: f64) -> ProviderResult<Forecast> {
+        let now = Utc::now().timestamp();
+        let today = now - now % SECONDS_PER_DAY;
+
+        let daily = (0..8)
+            .map(|day| DailyWeather {
+                dt: today + day * SECONDS_PER_DAY,
+                temp_day: 18.0 + (day % 3) as f64,
+                temp_min: 11.0 + (day % 2) as f64,
+                temp_max: 21.0 + (day % 4) as f64,
+                humidity: 60 + (day % 5) as i32 * 5,
+                precipitation: (day % 3) as f64 * 1.5,
+                condition: mock_condition(day),
+            })
+            .collect();
+
+        Ok(Forecast {
+            timezone_offset: 7200,
+            current: CurrentWeather {
+                dt: now,
+                sunrise: today + 5 * 3600,
+                sunset: today + 19 * 3600,
+                temp: 19.4,
+                feels_like: 18.7,
+                pressure: 1015,
+                humidity: 64,
+                clouds: 40,
+                wind_speed: 3.6,
+                wind_deg: 250,
+                condition: mock_condition(0),
+            },
+            daily,
+        })
+    }
+}
+
+fn mock_condition(day: i64) -> Condition {
+    let (description, icon) = match day % 4 {
+        0 => ("scattered clouds", "03d"),
+        1 => ("clear sky", "01d"),
+        2 => ("light rain", "10d"),
+        _ => ("overcast clouds", "04d"),
+    };
+
+    Condition {
+        description: description.to_string(),
+        icon: icon.to_string(),
+    }
+}
@@ -0,0 +1,125 @@
+mod cache;
+mod error;
+mod mock;
+mod open_meteo;
+mod openweathermap;
+
+pub use self::error::WeatherError;
+pub use self::mock::MockProvider;
+
+use self::cache::CachedProvider;
+use self::open_meteo::OpenMeteoProvider;
+use self::openweathermap::OpenWeatherMapProvider;
+use crate::core::{config::WeatherConfig, http::HttpClient};
+use log::info;
+use serenity::{async_trait, prelude::*};
+use std::{sync::Arc, time::Duration};
+
+pub type ProviderResult<T> = Result<T, WeatherError>;
+
+/// A source of weather data. Every provider returns the same provider independent data,
+/// so the embeds don't have to care about where it came from.
+#[async_trait]
+pub trait WeatherProvider: Send + Sync {
+    /// Name of the provider, shown in the embed footers
+    fn name(&self) -> &'static str;
+
+    /// Looks up the coordinates of the location matching the given search query
+    async fn find_location(&self, query: &str) -> ProviderResult<Location>;
+
+    /// Retrieves the current weather and the daily forecast at the given coordinates
+    async fn forecast(&self, lat: f64, lon: f64) -> ProviderResult<Forecast>;
+
+    /// Url of a transparent weather map tile to draw on top of the location map, if supported
+    fn map_tile_url(&self, _zoom: i32, _x: i32, _y: i32) -> Option<String> {
+        None
+    }
+}
+
+pub struct WeatherProviderContainer;
+impl TypeMapKey for WeatherProviderContainer {
+    type Value = Arc<dyn WeatherProvider>;
+}
+
+#[derive(Clone, Debug)]
+pub struct Location {
+    pub name: String,
+    pub lat: f64,
+    pub lon: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Forecast {
+    // Offset of the location's timezone to UTC in seconds
+    pub timezone_offset: i32,
+    pub current: CurrentWeather,
+    // Daily forecast, starting with today
+    pub daily: Vec<DailyWeather>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CurrentWeather {
+    pub dt: i64,
+    pub sunrise: i64,
+    pub sunset: i64,
+    pub temp: f64,
+    pub feels_like: f64,
+    pub pressure: i32,
+    pub humidity: i32,
+    pub clouds: i32,
+    pub wind_speed: f64,
+    pub wind_deg: i32,
+    pub condition: Condition,
+}
+
+#[derive(Clone, Debug)]
+pub struct DailyWeather {
+    pub dt: i64,
+    pub temp_day: f64,
+    pub temp_min: f64,
+    pub temp_max: f64,
+    pub humidity: i32,
+    // Rain and snow in mm
+    pub precipitation: f64,
+    pub condition: Condition,
+}
+
+#[derive(Clone, Debug)]
+pub struct Condition {
+    pub description: String,
+    // OpenWeatherMap icon code (e.g. "01d"), which all providers map their conditions to
+    pub icon: String,
+}
+
+impl Condition {
+    /// Placeholder for when a provider doesn't report any condition
+    pub fn unknown() -> Self {
+        Condition {
+            description: "unknown".to_string(),
+            icon: String::new(),
+        }
+    }
+}
+
+/// Creates the configured weather provider ("openweathermap", "open-meteo" or "mock"),
+/// wrapped in a cache. Without a selection, OpenWeatherMap is used if an api key is configured,
+/// falling back to the keyless Open-Meteo otherwise.
+pub fn create_provider(client: &HttpClient, config: &WeatherConfig) -> Arc<dyn WeatherProvider> {
+    let token = config.open_weather_map_token.clone();
+
+    // The selection has been checked when the config was loaded
+    let provider: Box<dyn WeatherProvider> = match (config.provider.as_deref(), token) {
+        (Some("mock"), _) => Box::new(MockProvider),
+        (Some("openweathermap"), Some(token)) | (None, Some(token)) => {
+            Box::new(OpenWeatherMapProvider::new(client.clone(), token))
+        }
+        _ => Box::new(OpenMeteoProvider::new(client.clone())),
+    };
+
+    info!("Using {} as weather provider", provider.name());
+
+    Arc::new(CachedProvider::new(
+        provider,
+        Duration::from_secs(config.cache_ttl),
+    ))
+}
@@ -0,0 +1,339 @@
+use super::error::parse_response;
+use super::{
+    Condition, CurrentWeather, DailyWeather, Forecast, Location, ProviderResult, WeatherError,
+    WeatherProvider,
+};
+use crate::core::http::{HttpClient, Service};
+use serde::Deserialize;
+use serenity::async_trait;
+use std::convert::TryFrom;
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+/// Keyless weather provider using the free Open-Meteo api (https://open-meteo.com/)
+pub struct OpenMeteoProvider {
+    client: HttpClient,
+}
+
+impl OpenMeteoProvider {
+    pub fn new(client: HttpClient) -> Self {
+        OpenMeteoProvider { client }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for OpenMeteoProvider {
+    fn name(&self) -> &'static str {
+        "Open-Meteo"
+    }
+
+    async fn find_location(&self, query: &str) -> ProviderResult<Location> {
+        let request = self
+            .client
+            .get("https://geocoding-api.open-meteo.com/v1/search")
+            .query(&[("name", query), ("count", "1")]);
+        let response = self.client.send(Service::Weather, request).await?;
+
+        let response: GeocodingResponse = parse_response(response, query).await?;
+
+        match response.results.into_iter().next() {
+            Some(result) => Ok(Location {
+                name: result.name,
+                lat: result.latitude,
+                lon: result.longitude,
+            }),
+            None => Err(WeatherError::LocationNotFound(query.to_string())),
+        }
+    }
+
+    async fn forecast(&self, lat: f64, lon: f64) -> ProviderResult<Forecast> {
+        let (lat, lon) = (lat.to_string(), lon.to_string());
+
+        let request = self.client
+            .get("https://api.open-meteo.com/v1/forecast")
+            .query(&[
+                ("latitude", lat.as_str()),
+                ("longitude", lon.as_str()),
+                ("current_weather", "true"),
+                (
+                    "hourly",
+                    "relativehumidity_2m,apparent_temperature,pressure_msl,cloudcover",
+                ),
+                (
+                    "daily",
+                    "weathercode,temperature_2m_max,temperature_2m_min,precipitation_sum,sunrise,sunset",
+                ),
+                ("windspeed_unit", "ms"),
+                ("timeformat", "unixtime"),
+                ("timezone", "auto"),
+            ]);
+        let response = self.client.send(Service::Weather, request).await?;
+
+        let response: ForecastResponse =
+            parse_response(response, &format!("{}, {}", lat, lon)).await?;
+
+        Forecast::try_from(response)
+    }
+}
+
+impl TryFrom<ForecastResponse> for Forecast {
+    type Error = WeatherError;
+
+    fn try_from(response: ForecastResponse) -> Result<Self, Self::Error> {
+        let current = &response.current_weather;
+        let hourly = &response.hourly;
+        let daily = &response.daily;
+
+        // The hourly values closest to the current time complete the current weather
+        let hourly_value = |name: &str, values: &[Option<f64>]| {
+            hourly
+                .time
+                .iter()
+                .zip(values)
+                .filter_map(|(&t, &value)| Some(((t - current.time).abs(), value?)))
+                .min_by_key(|&(distance, _)| distance)
+                .map(|(_, value)| value)
+                .ok_or_else(|| missing_value(name))
+        };
+
+        let sunrise = *daily
+            .sunrise
+            .first()
+            .ok_or_else(|| missing_value("sunrise"))?;
+        let sunset = *daily
+            .sunset
+            .first()
+            .ok_or_else(|| missing_value("sunset"))?;
+        let is_day = current.time >= sunrise && current.time < sunset;
+
+        // Days beyond the forecast range have no values, those are left out
+        let daily_weather = daily
+            .time
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &day_start)| {
+                let temp_min = daily.temperature_2m_min.get(i).copied().flatten()?;
+                let temp_max = daily.temperature_2m_max.get(i).copied().flatten()?;
+                let precipitation = daily.precipitation_sum.get(i).copied().flatten()?;
+
+                // There's no daily humidity, so average it over the hours of the day
+                let day_humidity: Vec<f64> = hourly
+                    .time
+                    .iter()
+                    .zip(&hourly.relativehumidity_2m)
+                    .filter(|(&t, _)| t >= day_start && t < day_start + SECONDS_PER_DAY)
+                    .filter_map(|(_, &humidity)| humidity)
+                    .collect();
+                if day_humidity.is_empty() {
+                    return None;
+                }
+                let humidity = day_humidity.iter().sum::<f64>() / day_humidity.len() as f64;
+
+                Some(DailyWeather {
+                    dt: day_start,
+                    temp_day: (temp_min + temp_max) / 2.0,
+                    temp_min,
+                    temp_max,
+                    humidity: humidity.round() as i32,
+                    precipitation,
+                    condition: match daily.weathercode.get(i).copied().flatten() {
+                        Some(code) => condition_from_wmo_code(code as i32, true),
+                        None => unknown_condition(),
+                    },
+                })
+            })
+            .collect();
+
+        Ok(Forecast {
+            timezone_offset: response.utc_offset_seconds,
+            current: CurrentWeather {
+                dt: current.time,
+                sunrise,
+                sunset,
+                temp: current.temperature,
+                feels_like: hourly_value("apparent temperature", &hourly.apparent_temperature)?,
+                pressure: hourly_value("pressure", &hourly.pressure_msl)?.round() as i32,
+                humidity: hourly_value("humidity", &hourly.relativehumidity_2m)?.round() as i32,
+                clouds: hourly_value("cloud cover", &hourly.cloudcover)?.round() as i32,
+                wind_speed: current.windspeed,
+                wind_deg: current.winddirection.round() as i32,
+                condition: condition_from_wmo_code(current.weathercode as i32, is_day),
+            },
+            daily: daily_weather,
+        })
+    }
+}
+
+// Without these the forecast can't be shown, rather than showing made up values
+fn missing_value(name: &str) -> WeatherError {
+    WeatherError::InvalidResponse(serde::de::Error::custom(format!(
+        "the forecast has no {}",
+        name
+    )))
+}
+
+// Maps WMO weather interpretation codes (https://open-meteo.com/en/docs) to a description
+// and the closest OpenWeatherMap icon
+fn condition_from_wmo_code(code: i32, is_day: bool) -> Condition {
+    let (description, icon) = match code {
+        0 => ("clear sky", "01"),
+        1 => ("mainly clear", "02"),
+        2 => ("partly cloudy", "03"),
+        3 => ("overcast", "04"),
+        45 | 48 => ("fog", "50"),
+        51 | 53 | 55 => ("drizzle", "09"),
+        56 | 57 => ("freezing drizzle", "09"),
+        61 => ("light rain", "10"),
+        63 => ("moderate rain", "10"),
+        65 => ("heavy rain", "10"),
+        66 | 67 => ("freezing rain", "10"),
+        71 => ("light snow", "13"),
+        73 => ("moderate snow", "13"),
+        75 => ("heavy snow", "13"),
+        77 => ("snow grains", "13"),
+        80..=82 => ("rain showers", "09"),
+        85 | 86 => ("snow showers", "13"),
+        95 => ("thunderstorm", "11"),
+        96 | 99 => ("thunderstorm with hail", "11"),
+        _ => return unknown_condition(),
+    };
+
+    Condition {
+        description: description.to_string(),
+        icon: format!("{}{}", icon, if is_day { "d" } else { "n" }),
+    }
+}
+
+// Codes this doesn't know get no icon, instead of one that might be wrong
+fn unknown_condition() -> Condition {
+    Condition {
+        description: "unknown conditions".to_string(),
+        icon: String::new(),
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct GeocodingResponse {
+    // Missing entirely when nothing was found
+    #[serde(default)]
+    results: Vec<GeocodingResult>,
+}
+#[derive(Deserialize, Debug)]
+struct GeocodingResult {
+    name: String,
+    latitude: f64,
+    longitude: f64,
+}
+
+#[derive(Deserialize, Debug)]
+struct ForecastResponse {
+    utc_offset_seconds: i32,
+    current_weather: CurrentWeatherResponse,
+    hourly: HourlyResponse,
+    daily: DailyResponse,
+}
+#[derive(Deserialize, Debug)]
+struct CurrentWeatherResponse {
+    time: i64,
+    temperature: f64,
+    windspeed: f64,
+    winddirection: f64,
+    weathercode: f64,
+}
+#[derive(Deserialize, Debug)]
+struct HourlyResponse {
+    time: Vec<i64>,
+    relativehumidity_2m: Vec<Option<f64>>,
+    apparent_temperature: Vec<Option<f64>>,
+    pressure_msl: Vec<Option<f64>>,
+    cloudcover: Vec<Option<f64>>,
+}
+#[derive(Deserialize, Debug)]
+struct DailyResponse {
+    time: Vec<i64>,
+    weathercode: Vec<Option<f64>>,
+    temperature_2m_max: Vec<Option<f64>>,
+    temperature_2m_min: Vec<Option<f64>>,
+    precipitation_sum: Vec<Option<f64>>,
+    sunrise: Vec<i64>,
+    sunset: Vec<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::error::parse_body;
+    use super::*;
+    use crate::core::testing::fixture;
+    use reqwest::StatusCode;
+
+    fn forecast_response() -> ForecastResponse {
+        parse_body(
+            StatusCode::OK,
+            None,
+            &fixture("weather/open_meteo_forecast.json"),
+            "52.52, 13.41",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn converts_the_forecast() {
+        let forecast = Forecast::try_from(forecast_response()).unwrap();
+
+        assert_eq!(forecast.timezone_offset, 7200);
+        assert_eq!(forecast.current.temp, 23.4);
+        assert_eq!(forecast.current.feels_like, 23.8);
+        assert_eq!(forecast.current.pressure, 1013);
+        assert_eq!(forecast.current.humidity, 62);
+        assert_eq!(forecast.current.clouds, 20);
+        assert_eq!(forecast.current.wind_deg, 248);
+        assert_eq!(forecast.current.condition.description, "partly cloudy");
+        assert_eq!(forecast.current.condition.icon, "03d");
+
+        assert_eq!(forecast.daily[1].temp_min, 15.2);
+        assert_eq!(forecast.daily[1].temp_max, 21.3);
+        assert_eq!(forecast.daily[1].precipitation, 4.3);
+        assert_eq!(forecast.daily[1].condition.description, "light rain");
+    }
+
+    #[test]
+    fn leaves_out_days_without_temperatures() {
+        let forecast = Forecast::try_from(forecast_response()).unwrap();
+
+        // The last day is past the end of the forecast, with all its values null
+        assert_eq!(forecast.daily.len(), 3);
+    }
+
+    #[test]
+    fn falls_back_to_the_closest_hour() {
+        let mut response = forecast_response();
+        // 16:00 local time, the hour of the current weather
+        response.hourly.pressure_msl[16] = None;
+        response.hourly.pressure_msl[15] = Some(1020.0);
+        response.hourly.pressure_msl[17] = Some(1030.0);
+        response.hourly.pressure_msl[18] = None;
+
+        let forecast = Forecast::try_from(response).unwrap();
+
+        assert_eq!(forecast.current.pressure, 1020);
+    }
+
+    #[test]
+    fn fails_without_current_values() {
+        let mut response = forecast_response();
+        response.hourly.relativehumidity_2m = vec![None; response.hourly.time.len()];
+
+        assert!(matches!(
+            Forecast::try_from(response),
+            Err(WeatherError::InvalidResponse(_))
+        ));
+    }
+
+    #[test]
+    fn gives_unknown_codes_no_icon() {
+        let condition = condition_from_wmo_code(42, true);
+
+        assert_eq!(condition.description, "unknown conditions");
+        assert!(condition.icon.is_empty());
+    }
+}
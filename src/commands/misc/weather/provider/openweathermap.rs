@@ -0,0 +1,237 @@
+use super::error::parse_response;
+use super::{
+    Condition, CurrentWeather, DailyWeather, Forecast, Location, ProviderResult, WeatherProvider,
+};
+use crate::core::http::{HttpClient, Service};
+use serde::Deserialize;
+use serenity::async_trait;
+
+// Weather map layer drawn on top of the location map
+const MAP_LAYER: &str = "precipitation_new";
+
+pub struct OpenWeatherMapProvider {
+    client: HttpClient,
+    token: String,
+}
+
+impl OpenWeatherMapProvider {
+    pub fn new(client: HttpClient, token: String) -> Self {
+        OpenWeatherMapProvider { client, token }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for OpenWeatherMapProvider {
+    fn name(&self) -> &'static str {
+        "OpenWeatherMap"
+    }
+
+    async fn find_location(&self, query: &str) -> ProviderResult<Location> {
+        let request = self
+            .client
+            .get("http://api.openweathermap.org/data/2.5/weather")
+            .query(&[("appid", self.token.as_str()), ("q", query)]);
+        let response = self.client.send(Service::Weather, request).await?;
+
+        let location: LocationQueryResponse = parse_response(response, query).await?;
+
+        Ok(Location {
+            name: location.name,
+            lat: location.coord.lat,
+            lon: location.coord.lon,
+        })
+    }
+
+    async fn forecast(&self, lat: f64, lon: f64) -> ProviderResult<Forecast> {
+        let (lat, lon) = (lat.to_string(), lon.to_string());
+
+        let request = self
+            .client
+            .get("http://api.openweathermap.org/data/2.5/onecall")
+            .query(&[
+                ("appid", self.token.as_str()),
+                ("lat", lat.as_str()),
+                ("lon", lon.as_str()),
+                ("units", "metric"),
+            ]);
+        let response = self.client.send(Service::Weather, request).await?;
+
+        let weather: WeatherQueryResponse =
+            parse_response(response, &format!("{}, {}", lat, lon)).await?;
+
+        Ok(weather.into())
+    }
+
+    fn map_tile_url(&self, zoom: i32, x: i32, y: i32) -> Option<String> {
+        Some(format!(
+            "https://tile.openweathermap.org/map/{layer}/{z}/{x}/{y}.png?appid={api_key}",
+            layer = MAP_LAYER,
+            z = zoom,
+            x = x,
+            y = y,
+            api_key = self.token
+        ))
+    }
+}
+
+impl From<WeatherQueryResponse> for Forecast {
+    fn from(weather: WeatherQueryResponse) -> Self {
+        let current = weather.current;
+
+        Forecast {
+            timezone_offset: weather.timezone_offset,
+            current: CurrentWeather {
+                dt: current.dt,
+                sunrise: current.sunrise,
+                sunset: current.sunset,
+                temp: current.temp,
+                feels_like: current.feels_like,
+                pressure: current.pressure,
+                humidity: current.humidity,
+                clouds: current.clouds,
+                wind_speed: current.wind_speed,
+                wind_deg: current.wind_deg,
+                condition: first_condition(current.weather),
+            },
+            daily: weather
+                .daily
+                .into_iter()
+                .map(|day| DailyWeather {
+                    dt: day.dt,
+                    temp_day: day.temp.day,
+                    temp_min: day.temp.min,
+                    temp_max: day.temp.max,
+                    humidity: day.humidity,
+                    precipitation: day.rain.unwrap_or_default() + day.snow.unwrap_or_default(),
+                    condition: first_condition(day.weather),
+                })
+                .collect(),
+        }
+    }
+}
+
+// OpenWeatherMap lists the most relevant condition first, but the list can also be empty
+fn first_condition(conditions: Vec<Weather>) -> Condition {
+    match conditions.into_iter().next() {
+        Some(weather) => Condition {
+            description: weather.description,
+            icon: weather.icon,
+        },
+        None => Condition::unknown(),
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct LocationQueryResponse {
+    name: String,
+    coord: Coordinates,
+    // Skip all the other data
+}
+#[derive(Deserialize, Debug)]
+struct Coordinates {
+    lon: f64,
+    lat: f64,
+}
+
+#[derive(Deserialize, Debug)]
+struct WeatherQueryResponse {
+    timezone_offset: i32,
+    current: CurrentWeatherResponse,
+    #[serde(default)]
+    daily: Vec<DailyWeatherResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CurrentWeatherResponse {
+    dt: i64,
+    sunrise: i64,
+    sunset: i64,
+    temp: f64,
+    feels_like: f64,
+    pressure: i32,
+    humidity: i32,
+    clouds: i32,
+    wind_speed: f64,
+    wind_deg: i32,
+    #[serde(default)]
+    weather: Vec<Weather>,
+}
+#[derive(Deserialize, Debug)]
+struct DailyWeatherResponse {
+    dt: i64,
+    temp: Temp,
+    humidity: i32,
+    rain: Option<f64>,
+    snow: Option<f64>,
+    #[serde(default)]
+    weather: Vec<Weather>,
+}
+#[derive(Deserialize, Debug)]
+struct Weather {
+    description: String,
+    icon: String,
+}
+#[derive(Deserialize, Debug)]
+struct Temp {
+    day: f64,
+    min: f64,
+    max: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::error::parse_body;
+    use super::*;
+    use crate::core::testing::fixture;
+    use reqwest::StatusCode;
+
+    fn parse_forecast(path: &str) -> Forecast {
+        let response: WeatherQueryResponse =
+            parse_body(StatusCode::OK, None, &fixture(path), "52.52, 13.405").unwrap();
+
+        response.into()
+    }
+
+    #[test]
+    fn parses_the_location() {
+        let location: LocationQueryResponse = parse_body(
+            StatusCode::OK,
+            None,
+            &fixture("weather/location.json"),
+            "Berlin",
+        )
+        .unwrap();
+
+        assert_eq!(location.name, "Berlin");
+        assert_eq!((location.coord.lat, location.coord.lon), (52.5244, 13.4105));
+    }
+
+    #[test]
+    fn parses_the_forecast() {
+        let forecast = parse_forecast("weather/onecall.json");
+
+        assert_eq!(forecast.timezone_offset, 7200);
+        assert_eq!(forecast.current.temp, 24.31);
+        assert_eq!(forecast.current.wind_deg, 250);
+        assert_eq!(forecast.current.condition.description, "scattered clouds");
+        assert_eq!(forecast.current.condition.icon, "03d");
+
+        assert_eq!(forecast.daily.len(), 3);
+        assert_eq!(forecast.daily[1].temp_min, 16.4);
+        assert_eq!(forecast.daily[1].temp_max, 23.7);
+        assert_eq!(forecast.daily[1].precipitation, 6.47);
+        assert_eq!(forecast.daily[1].condition.description, "moderate rain");
+        // Days without rain or snow leave both out
+        assert_eq!(forecast.daily[2].precipitation, 0.0);
+    }
+
+    #[test]
+    fn parses_a_forecast_without_conditions() {
+        let forecast = parse_forecast("weather/onecall_no_conditions.json");
+
+        assert_eq!(forecast.current.condition.description, "unknown");
+        assert_eq!(forecast.current.condition.icon, "");
+        assert_eq!(forecast.daily[0].condition.description, "unknown");
+        assert_eq!(forecast.daily[0].precipitation, 0.4);
+    }
+}
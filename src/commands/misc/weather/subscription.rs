@@ -0,0 +1,465 @@
+use super::provider::{Forecast, WeatherProvider, WeatherProviderContainer};
+use super::{
+    format_timestamp, get_provider, get_weather_emoji, get_weather_image_url, query_forecast,
+    reply_with_error, uppercase_first,
+};
+use crate::core::{
+    config::{get_config, ConfigContainer},
+    consts::MAIN_COLOR,
+    sink::ChannelSink,
+    store::JsonStore,
+};
+use chrono::{Duration, NaiveDateTime, NaiveTime, Timelike, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serenity::{
+    builder::CreateEmbed,
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    http::Http,
+    model::{
+        channel::Message,
+        id::{ChannelId, UserId},
+    },
+    prelude::*,
+};
+use std::sync::Arc;
+
+// How often the scheduler checks for subscriptions that are due
+const SCHEDULER_INTERVAL_SECS: u64 = 30;
+
+// If the bot was offline at the scheduled time, a digest is still delivered up to this many minutes late
+const DELIVERY_GRACE_MINUTES: i64 = 60;
+
+pub struct WeatherSubscriptionsContainer;
+impl TypeMapKey for WeatherSubscriptionsContainer {
+    type Value = Arc<RwLock<JsonStore<WeatherSubscriptions>>>;
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct WeatherSubscriptions {
+    next_id: u64,
+    subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Subscription {
+    id: u64,
+    owner: UserId,
+    target: SubscriptionTarget,
+    location: String,
+    lat: f64,
+    lon: f64,
+    hour: u32,
+    minute: u32,
+    // Offset of the location's timezone to UTC in seconds, refreshed on every delivery
+    timezone_offset: i32,
+    // Local time the last delivered digest was scheduled for, so each one is only delivered once
+    last_sent: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+enum SubscriptionTarget {
+    User(UserId),
+    Channel(ChannelId),
+}
+
+impl Subscription {
+    fn local_now(&self) -> NaiveDateTime {
+        Utc::now().naive_utc() + Duration::seconds(self.timezone_offset as i64)
+    }
+
+    // The latest time the digest was scheduled for, which is yesterday's while today's is still ahead
+    fn last_scheduled(&self) -> NaiveDateTime {
+        let local_now = self.local_now();
+        let scheduled = local_now.date().and_hms(self.hour, self.minute, 0);
+
+        match scheduled > local_now {
+            true => scheduled - Duration::days(1),
+            false => scheduled,
+        }
+    }
+
+    fn is_due(&self) -> bool {
+        let scheduled = self.last_scheduled();
+
+        self.local_now() - scheduled < Duration::minutes(DELIVERY_GRACE_MINUTES)
+            && !self.was_sent(scheduled)
+    }
+
+    fn was_sent(&self, scheduled: NaiveDateTime) -> bool {
+        self.last_sent == Some(scheduled.to_string())
+    }
+
+    fn describe(&self) -> String {
+        let target = match self.target {
+            SubscriptionTarget::User(_) => "via DM".to_string(),
+            SubscriptionTarget::Channel(channel_id) => format!("in <#{}>", channel_id.0),
+        };
+
+        format!(
+            "`#{}` **{}** at {:02}:{:02} local time, {}",
+            self.id, self.location, self.hour, self.minute, target
+        )
+    }
+}
+
+#[command]
+#[min_args(2)]
+#[description(
+    "Subscribes you to a daily weather digest for the given location, delivered via DM \
+    at the given local time of that location. \
+    Mention a channel first to deliver it there instead (requires the Manage Channels permission)."
+)]
+#[usage("[#channel] <location> <HH:MM>")]
+#[example("Berlin 07:30")]
+#[example("#general New York 08:00")]
+pub async fn subscribe(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    // If a channel is mentioned first, the digest goes there instead of the user's DMs
+    let target = match args.single::<ChannelId>() {
+        Ok(channel_id) => {
+            check_can_manage_channel(ctx, msg, channel_id).await?;
+            SubscriptionTarget::Channel(channel_id)
+        }
+        Err(_) => SubscriptionTarget::User(msg.author.id),
+    };
+
+    // The time is always the last argument, everything in front of it is the location
+    let (location_arg, time_arg) = match args.rest().trim().rsplit_once(' ') {
+        Some((location, time)) => (location.trim().to_string(), time.to_string()),
+        None => {
+            return Err(CommandError::from(
+                "You need to pass both a location and a time, e.g. `Berlin 07:30`",
+            ))
+        }
+    };
+
+    let time = match NaiveTime::parse_from_str(&time_arg, "%H:%M") {
+        Ok(time) => time,
+        Err(_) => {
+            return Err(CommandError::from(
+                "The time needs to be given in 24h format, e.g. `07:30`",
+            ))
+        }
+    };
+
+    let provider = get_provider(ctx).await;
+    let (location, weather) = match query_forecast(provider.as_ref(), &location_arg).await {
+        Ok(result) => result,
+        Err(why) => {
+            let sink = ChannelSink::new(&ctx.http, msg.channel_id);
+            return reply_with_error(&sink, why).await;
+        }
+    };
+
+    let subscription = {
+        let store = get_store(ctx).await;
+        let mut store = store.write().await;
+
+        store.update(|data| {
+            let subscription = Subscription {
+                id: data.next_id,
+                owner: msg.author.id,
+                target,
+                location: location.name.clone(),
+                lat: location.lat,
+                lon: location.lon,
+                hour: time.hour(),
+                minute: time.minute(),
+                timezone_offset: weather.timezone_offset,
+                last_sent: None,
+            };
+
+            data.next_id += 1;
+            data.subscriptions.push(subscription.clone());
+
+            subscription
+        })?
+    };
+
+    let config = get_config(ctx).await;
+    let _ = msg
+        .channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.colour(MAIN_COLOR)
+                    .title("Subscribed to the daily weather")
+                    .description(format!(
+                        "{}\n\nUse `{}weather unsubscribe {}` to stop it again.",
+                        subscription.describe(),
+                        config.prefix(),
+                        subscription.id
+                    ))
+            })
+        })
+        .await;
+
+    Ok(())
+}
+
+#[command]
+#[num_args(1)]
+#[description("Removes one of your daily weather subscriptions")]
+#[usage("<subscription id>")]
+#[example("3")]
+pub async fn unsubscribe(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let id = match args.single::<u64>() {
+        Ok(id) => id,
+        Err(_) => {
+            return Err(CommandError::from(format!(
+                "You need to pass the id of the subscription, see `{}weather subscriptions`",
+                get_config(ctx).await.prefix()
+            )))
+        }
+    };
+
+    let removed = {
+        let store = get_store(ctx).await;
+        let mut store = store.write().await;
+
+        store.update(|data| {
+            let position = data
+                .subscriptions
+                .iter()
+                .position(|s| s.id == id && s.owner == msg.author.id)?;
+
+            Some(data.subscriptions.remove(position))
+        })?
+    };
+
+    let _ = msg
+        .channel_id
+        .send_message(&ctx.http, |m| match removed {
+            Some(subscription) => {
+                m.content(format!("Unsubscribed from {}", subscription.describe()))
+            }
+            None => m.content(format!(
+                "You don't have a subscription with the id `{}`",
+                id
+            )),
+        })
+        .await;
+
+    Ok(())
+}
+
+#[command]
+#[aliases("list")]
+#[description(
+    "Lists your daily weather subscriptions, as well as the ones delivered to this channel"
+)]
+pub async fn subscriptions(ctx: &Context, msg: &Message, _: Args) -> CommandResult {
+    let descriptions: Vec<String> = {
+        let store = get_store(ctx).await;
+        let store = store.read().await;
+
+        store
+            .get()
+            .subscriptions
+            .iter()
+            .filter(|s| {
+                s.owner == msg.author.id || s.target == SubscriptionTarget::Channel(msg.channel_id)
+            })
+            .map(|s| s.describe())
+            .collect()
+    };
+
+    let config = get_config(ctx).await;
+    let _ = msg
+        .channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.colour(MAIN_COLOR)
+                    .title("Daily weather subscriptions")
+                    .description(match descriptions.is_empty() {
+                        true => format!(
+                            "There are no subscriptions yet. \
+                            Create one with `{}weather subscribe <location> <HH:MM>`!",
+                            config.prefix()
+                        ),
+                        false => descriptions.join("\n"),
+                    })
+            })
+        })
+        .await;
+
+    Ok(())
+}
+
+/// Periodically delivers all weather subscriptions that are due. Since subscriptions and their
+/// last delivery are persisted, any digest missed during a short downtime is caught up on.
+pub async fn run_subscription_scheduler(http: Arc<Http>, data: Arc<RwLock<TypeMap>>) {
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs(SCHEDULER_INTERVAL_SECS));
+
+    loop {
+        interval.tick().await;
+
+        let (store, provider, config) = {
+            let data = data.read().await;
+
+            match (
+                data.get::<WeatherSubscriptionsContainer>(),
+                data.get::<WeatherProviderContainer>(),
+                data.get::<ConfigContainer>(),
+            ) {
+                (Some(store), Some(provider), Some(config)) => {
+                    (Arc::clone(store), Arc::clone(provider), Arc::clone(config))
+                }
+                _ => continue,
+            }
+        };
+
+        let due: Vec<Subscription> = store
+            .read()
+            .await
+            .get()
+            .subscriptions
+            .iter()
+            .filter(|s| s.is_due())
+            .cloned()
+            .collect();
+
+        for subscription in due {
+            info!("Delivering weather subscription #{}", subscription.id);
+            let scheduled = subscription.last_scheduled();
+
+            // Mark the subscription as delivered even if it fails, so it isn't retried every tick
+            let timezone_offset =
+                match deliver(&http, provider.as_ref(), &subscription, config.prefix()).await {
+                    Ok(timezone_offset) => timezone_offset,
+                    Err(why) => {
+                        warn!(
+                            "Could not deliver weather subscription #{}: {:?}",
+                            subscription.id, why
+                        );
+                        subscription.timezone_offset
+                    }
+                };
+
+            let result = store.write().await.update(|data| {
+                if let Some(s) = data
+                    .subscriptions
+                    .iter_mut()
+                    .find(|s| s.id == subscription.id)
+                {
+                    s.last_sent = Some(scheduled.to_string());
+                    s.timezone_offset = timezone_offset;
+                }
+            });
+
+            if let Err(why) = result {
+                warn!("Could not save weather subscriptions: {:?}", why);
+            }
+        }
+    }
+}
+
+// Sends the digest for a subscription and returns the current timezone offset of its location
+async fn deliver(
+    http: &Http,
+    provider: &dyn WeatherProvider,
+    subscription: &Subscription,
+    prefix: &str,
+) -> Result<i32, CommandError> {
+    let weather = provider
+        .forecast(subscription.lat, subscription.lon)
+        .await?;
+
+    let channel_id = match subscription.target {
+        SubscriptionTarget::Channel(channel_id) => channel_id,
+        SubscriptionTarget::User(user_id) => user_id.create_dm_channel(http).await?.id,
+    };
+
+    channel_id
+        .send_message(http, |m| {
+            m.embed(|e| build_digest_embed(e, &subscription.location, &weather, prefix))
+        })
+        .await?;
+
+    Ok(weather.timezone_offset)
+}
+
+fn build_digest_embed<'a>(
+    e: &'a mut CreateEmbed,
+    location: &str,
+    weather: &Forecast,
+    prefix: &str,
+) -> &'a mut CreateEmbed {
+    e.colour(MAIN_COLOR)
+        .title(format!("Today's weather in {}", location))
+        .footer(|f| {
+            f.text(format!(
+                "Daily weather subscription, see {}weather subscriptions",
+                prefix
+            ))
+        });
+
+    if let Some(today) = weather.daily.first() {
+        if let Some(image_url) = get_weather_image_url(&today.condition.icon) {
+            e.thumbnail(image_url);
+        }
+
+        e.description(format!(
+            "{} **{}** \n\
+            **Temp**: {:.0}°C to {:.0}°C\n\
+            **Humidity**: {}%\n\
+            **Precipitation**: {:.1} mm",
+            get_weather_emoji(&today.condition.icon),
+            uppercase_first(&today.condition.description),
+            today.temp_min,
+            today.temp_max,
+            today.humidity,
+            today.precipitation
+        ));
+    }
+
+    if let Some(tomorrow) = weather.daily.get(1) {
+        e.field(
+            format!(
+                "Tomorrow, {}",
+                format_timestamp(tomorrow.dt, weather.timezone_offset, "%e %b")
+            ),
+            format!(
+                "{} **{}**, {:.0}°C to {:.0}°C",
+                get_weather_emoji(&tomorrow.condition.icon),
+                uppercase_first(&tomorrow.condition.description),
+                tomorrow.temp_min,
+                tomorrow.temp_max
+            ),
+            false,
+        );
+    }
+
+    e
+}
+
+async fn get_store(ctx: &Context) -> Arc<RwLock<JsonStore<WeatherSubscriptions>>> {
+    let data = ctx.data.read().await;
+
+    Arc::clone(data.get::<WeatherSubscriptionsContainer>().unwrap())
+}
+
+// Only members allowed to manage a channel may have digests delivered into it
+async fn check_can_manage_channel(
+    ctx: &Context,
+    msg: &Message,
+    channel_id: ChannelId,
+) -> Result<(), CommandError> {
+    let channel = match channel_id.to_channel(ctx).await?.guild() {
+        Some(channel) if Some(channel.guild_id) == msg.guild_id => channel,
+        _ => {
+            return Err(CommandError::from(
+                "The channel needs to be a text channel of this server",
+            ))
+        }
+    };
+
+    let permissions = channel.permissions_for_user(ctx, msg.author.id).await?;
+
+    match permissions.manage_channels() {
+        true => Ok(()),
+        false => Err(CommandError::from(
+            "You need the Manage Channels permission to subscribe a channel",
+        )),
+    }
+}
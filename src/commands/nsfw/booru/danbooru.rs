@@ -0,0 +1,103 @@
+use super::{split_tags, Booru, Post, PostQuery, Rating};
+use crate::core::http::{HttpClient, Service};
+use serde::Deserialize;
+use serenity::async_trait;
+
+const BASE_URL: &str = "https://danbooru.donmai.us";
+
+pub struct Danbooru {
+    client: HttpClient,
+}
+
+impl Danbooru {
+    pub fn new(client: HttpClient) -> Self {
+        Danbooru { client }
+    }
+}
+
+#[async_trait]
+impl Booru for Danbooru {
+    fn name(&self) -> &'static str {
+        "danbooru"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["dan"]
+    }
+
+    fn color(&self) -> u32 {
+        0x0073FF
+    }
+
+    // Without an account, searches are limited to two tags
+    fn max_tags(&self) -> Option<usize> {
+        Some(2)
+    }
+
+    async fn posts(&self, query: &PostQuery) -> reqwest::Result<Vec<Post>> {
+        let mut tags = query.tags.clone();
+        if let Some(rating) = query.rating {
+            tags.push(
+                match rating {
+                    // Danbooru splits safe posts into general and sensitive ones
+                    Rating::Safe => "rating:g,s",
+                    Rating::Questionable => "rating:q",
+                    Rating::Explicit => "rating:e",
+                }
+                .to_string(),
+            );
+        }
+
+        let mut params = vec![("tags", tags.join(" ")), ("limit", query.limit.to_string())];
+        if query.random {
+            params.push(("random", "true".to_string()));
+        }
+
+        let request = self
+            .client
+            .get(format!("{}/posts.json", BASE_URL).as_str())
+            .query(&params);
+
+        let posts: Vec<DanbooruPost> = self
+            .client
+            .send(Service::Booru, request)
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(posts
+            .into_iter()
+            // Posts without an id are ones deleted or hidden from anonymous users
+            .filter_map(|post| {
+                let id = post.id?;
+
+                Some(Post {
+                    id,
+                    url: format!("{}/posts/{}", BASE_URL, id),
+                    file_url: post.large_file_url.or(post.file_url),
+                    tags: split_tags(&post.tag_string_general),
+                    artists: split_tags(&post.tag_string_artist),
+                    rating: post.rating.as_deref().and_then(Rating::from_name),
+                    score: post.score,
+                    source: post.source,
+                })
+            })
+            .collect())
+    }
+}
+
+#[derive(Deserialize)]
+struct DanbooruPost {
+    id: Option<u64>,
+    file_url: Option<String>,
+    large_file_url: Option<String>,
+    #[serde(default)]
+    tag_string_general: String,
+    #[serde(default)]
+    tag_string_artist: String,
+    rating: Option<String>,
+    #[serde(default)]
+    score: i64,
+    source: Option<String>,
+}
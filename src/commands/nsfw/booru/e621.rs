@@ -0,0 +1,110 @@
+use super::{Booru, Post, PostQuery, Rating};
+use crate::core::http::{HttpClient, Service};
+use serde::Deserialize;
+use serenity::async_trait;
+
+const BASE_URL: &str = "https://e621.net";
+
+pub struct E621 {
+    client: HttpClient,
+}
+
+impl E621 {
+    pub fn new(client: HttpClient) -> Self {
+        E621 { client }
+    }
+}
+
+#[async_trait]
+impl Booru for E621 {
+    fn name(&self) -> &'static str {
+        "e621"
+    }
+
+    fn color(&self) -> u32 {
+        0x00549E
+    }
+
+    async fn posts(&self, query: &PostQuery) -> reqwest::Result<Vec<Post>> {
+        let mut tags = query.tags.clone();
+        if let Some(rating) = query.rating {
+            tags.push(
+                match rating {
+                    Rating::Safe => "rating:s",
+                    Rating::Questionable => "rating:q",
+                    Rating::Explicit => "rating:e",
+                }
+                .to_string(),
+            );
+        }
+        if query.random {
+            tags.push("order:random".to_string());
+        }
+
+        let params = [("tags", tags.join(" ")), ("limit", query.limit.to_string())];
+
+        let request = self
+            .client
+            .get(format!("{}/posts.json", BASE_URL).as_str())
+            .query(&params);
+
+        let response: E621Response = self
+            .client
+            .send(Service::Booru, request)
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(response
+            .posts
+            .into_iter()
+            .map(|post| Post {
+                id: post.id,
+                url: format!("{}/posts/{}", BASE_URL, post.id),
+                file_url: post.sample.url.or(post.file.url),
+                tags: post.tags.general,
+                artists: post.tags.artist,
+                rating: Rating::from_name(&post.rating),
+                score: post.score.total,
+                source: post.sources.into_iter().next(),
+            })
+            .collect())
+    }
+}
+
+#[derive(Deserialize)]
+struct E621Response {
+    posts: Vec<E621Post>,
+}
+
+#[derive(Deserialize)]
+struct E621Post {
+    id: u64,
+    file: E621File,
+    sample: E621File,
+    tags: E621Tags,
+    rating: String,
+    score: E621Score,
+    #[serde(default)]
+    sources: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct E621File {
+    // Missing for posts hidden from anonymous users
+    url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct E621Tags {
+    #[serde(default)]
+    general: Vec<String>,
+    #[serde(default)]
+    artist: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct E621Score {
+    total: i64,
+}
@@ -0,0 +1,105 @@
+use super::{split_tags, Booru, Post, PostQuery, Rating};
+use crate::core::http::{HttpClient, Service};
+use serde::Deserialize;
+use serenity::async_trait;
+
+const BASE_URL: &str = "https://gelbooru.com/index.php";
+
+pub struct Gelbooru {
+    client: HttpClient,
+}
+
+impl Gelbooru {
+    pub fn new(client: HttpClient) -> Self {
+        Gelbooru { client }
+    }
+}
+
+#[async_trait]
+impl Booru for Gelbooru {
+    fn name(&self) -> &'static str {
+        "gelbooru"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["gel"]
+    }
+
+    fn color(&self) -> u32 {
+        0x006FFA
+    }
+
+    async fn posts(&self, query: &PostQuery) -> reqwest::Result<Vec<Post>> {
+        let mut tags = query.tags.clone();
+        if let Some(rating) = query.rating {
+            tags.push(
+                match rating {
+                    // Gelbooru splits safe posts into general and sensitive ones
+                    Rating::Safe => "-rating:questionable -rating:explicit",
+                    Rating::Questionable => "rating:questionable",
+                    Rating::Explicit => "rating:explicit",
+                }
+                .to_string(),
+            );
+        }
+        if query.random {
+            tags.push("sort:random".to_string());
+        }
+
+        let params = [
+            ("page", "dapi".to_string()),
+            ("s", "post".to_string()),
+            ("q", "index".to_string()),
+            ("json", "1".to_string()),
+            ("tags", tags.join(" ")),
+            ("limit", query.limit.to_string()),
+        ];
+
+        let request = self.client.get(BASE_URL).query(&params);
+
+        let response: GelbooruResponse = self
+            .client
+            .send(Service::Booru, request)
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(response
+            .post
+            .into_iter()
+            .map(|post| Post {
+                id: post.id,
+                url: format!("{}?page=post&s=view&id={}", BASE_URL, post.id),
+                file_url: Some(post.file_url).filter(|url| !url.is_empty()),
+                tags: split_tags(&post.tags),
+                // Gelbooru doesn't tell the artist apart from the other tags
+                artists: Vec::new(),
+                rating: Rating::from_name(&post.rating),
+                score: post.score.unwrap_or(0),
+                source: Some(post.source),
+            })
+            .collect())
+    }
+}
+
+#[derive(Deserialize)]
+struct GelbooruResponse {
+    // Missing if there are no results
+    #[serde(default)]
+    post: Vec<GelbooruPost>,
+}
+
+#[derive(Deserialize)]
+struct GelbooruPost {
+    id: u64,
+    #[serde(default)]
+    file_url: String,
+    #[serde(default)]
+    tags: String,
+    #[serde(default)]
+    rating: String,
+    score: Option<i64>,
+    #[serde(default)]
+    source: String,
+}
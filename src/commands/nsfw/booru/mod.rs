@@ -0,0 +1,320 @@
+mod danbooru;
+mod e621;
+mod gelbooru;
+
+use self::danbooru::Danbooru;
+use self::e621::E621;
+use self::gelbooru::Gelbooru;
+use crate::core::{checks::ISNSFW_CHECK, http::HttpClient, pagination::paginator::send_paginated};
+use serenity::{
+    async_trait,
+    builder::CreateEmbed,
+    client::Context,
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    model::channel::Message,
+    prelude::*,
+};
+use std::sync::Arc;
+
+// How many posts a search shows at most
+const SEARCH_LIMIT: u32 = 20;
+
+// Discord's limit for embed field values
+const FIELD_VALUE_LIMIT: usize = 1024;
+
+/// An image board. Implementing this is all it takes to add a site to the booru commands.
+#[async_trait]
+pub trait Booru: Send + Sync {
+    /// Name the site is chosen by in commands, like "danbooru"
+    fn name(&self) -> &'static str;
+
+    /// Other names the site can be chosen by
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Color of the post embeds
+    fn color(&self) -> u32;
+
+    /// How many tags a search can have at most, counting the rating filter as one
+    fn max_tags(&self) -> Option<usize> {
+        None
+    }
+
+    /// Finds the posts matching the query, newest first unless a random order is requested
+    async fn posts(&self, query: &PostQuery) -> reqwest::Result<Vec<Post>>;
+}
+
+pub struct BooruRegistryContainer;
+impl TypeMapKey for BooruRegistryContainer {
+    type Value = Arc<BooruRegistry>;
+}
+
+pub struct BooruRegistry {
+    boorus: Vec<Box<dyn Booru>>,
+}
+
+impl BooruRegistry {
+    pub fn new(client: &HttpClient) -> Self {
+        BooruRegistry {
+            boorus: vec![
+                Box::new(Danbooru::new(client.clone())),
+                Box::new(Gelbooru::new(client.clone())),
+                Box::new(E621::new(client.clone())),
+            ],
+        }
+    }
+
+    fn find(&self, name: &str) -> Option<&dyn Booru> {
+        let name = name.to_lowercase();
+
+        self.boorus
+            .iter()
+            .find(|booru| booru.name() == name || booru.aliases().contains(&name.as_str()))
+            .map(|booru| booru.as_ref())
+    }
+
+    fn names(&self) -> Vec<&'static str> {
+        self.boorus.iter().map(|booru| booru.name()).collect()
+    }
+}
+
+pub struct PostQuery {
+    pub tags: Vec<String>,
+    pub rating: Option<Rating>,
+    pub random: bool,
+    pub limit: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Rating {
+    Safe,
+    Questionable,
+    Explicit,
+}
+
+impl Rating {
+    /// Parses the rating names and abbreviations the sites use, like "s", "safe" or "general"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "s" | "safe" | "g" | "general" | "sensitive" => Some(Rating::Safe),
+            "q" | "questionable" => Some(Rating::Questionable),
+            "e" | "explicit" => Some(Rating::Explicit),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Rating::Safe => "Safe",
+            Rating::Questionable => "Questionable",
+            Rating::Explicit => "Explicit",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Post {
+    pub id: u64,
+    // Page of the post on the site
+    pub url: String,
+    // Full size image. Some posts hide it from anonymous users.
+    pub file_url: Option<String>,
+    pub tags: Vec<String>,
+    pub artists: Vec<String>,
+    pub rating: Option<Rating>,
+    pub score: i64,
+    pub source: Option<String>,
+}
+
+#[command]
+#[checks("IsNSFW")]
+#[min_args(1)]
+#[sub_commands(random)]
+#[description(
+    "Searches an image board for posts with all of the given tags. \
+    Filter by rating with `rating:safe`, `rating:questionable` or `rating:explicit`. \
+    The `random` subcommand shows a single random post instead."
+)]
+#[usage("<site> [rating:<rating>] [tags]")]
+#[example("danbooru rating:safe hatsune_miku")]
+pub async fn booru(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    show_posts(ctx, msg, args, false).await
+}
+
+#[command]
+#[checks("IsNSFW")]
+#[min_args(1)]
+#[description("Shows a random post from an image board, optionally with the given tags")]
+#[usage("<site> [rating:<rating>] [tags]")]
+#[example("gelbooru cat_ears")]
+pub async fn random(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    show_posts(ctx, msg, args, true).await
+}
+
+async fn show_posts(ctx: &Context, msg: &Message, mut args: Args, random: bool) -> CommandResult {
+    let registry = {
+        let data = ctx.data.read().await;
+        Arc::clone(data.get::<BooruRegistryContainer>().unwrap())
+    };
+
+    let site = args.single::<String>()?;
+    let booru = match registry.find(&site) {
+        Some(booru) => booru,
+        None => {
+            return Err(CommandError::from(format!(
+                "Unknown site '{}', use one of {}",
+                site,
+                registry.names().join(", ")
+            )))
+        }
+    };
+
+    let mut query = PostQuery {
+        tags: Vec::new(),
+        rating: None,
+        random,
+        limit: if random { 1 } else { SEARCH_LIMIT },
+    };
+
+    for arg in args.iter::<String>().filter_map(Result::ok) {
+        match arg.strip_prefix("rating:") {
+            Some(rating) => match Rating::from_name(rating) {
+                Some(rating) => query.rating = Some(rating),
+                None => {
+                    return Err(CommandError::from(
+                        "The rating must be safe, questionable or explicit",
+                    ))
+                }
+            },
+            None => query.tags.push(arg.to_lowercase()),
+        }
+    }
+
+    check_tag_count(booru, &query)?;
+
+    let posts = booru.posts(&query).await?;
+
+    if posts.is_empty() {
+        let _ = msg
+            .channel_id
+            .send_message(&ctx.http, |m| match query.tags.is_empty() {
+                true => m.content("Could not find any posts"),
+                false => m.content(format!(
+                    "Could not find any posts matching '{}'",
+                    query.tags.join(" ")
+                )),
+            })
+            .await;
+
+        return Ok(());
+    }
+
+    let pages: Vec<CreateEmbed> = posts.iter().map(|post| post_embed(booru, post)).collect();
+
+    send_paginated(ctx, msg.channel_id, msg.author.id, pages).await
+}
+
+// Sites reject searches with too many tags with an error that doesn't say why
+fn check_tag_count(booru: &dyn Booru, query: &PostQuery) -> CommandResult {
+    let max_tags = match booru.max_tags() {
+        Some(max_tags) => max_tags,
+        None => return Ok(()),
+    };
+
+    let tag_count = query.tags.len() + query.rating.map_or(0, |_| 1);
+    if tag_count <= max_tags {
+        return Ok(());
+    }
+
+    Err(CommandError::from(format!(
+        "{} only allows searching for {} tags at once, and a rating counts as one",
+        booru.name(),
+        max_tags
+    )))
+}
+
+fn post_embed(booru: &dyn Booru, post: &Post) -> CreateEmbed {
+    let mut embed = CreateEmbed::default();
+    embed
+        .color(booru.color())
+        .title(format!("{} #{}", booru.name(), post.id))
+        .url(&post.url);
+
+    match &post.file_url {
+        Some(file_url) => embed.image(file_url),
+        None => embed.description("The image is only visible on the site"),
+    };
+
+    if !post.artists.is_empty() {
+        embed.field("Artist", post.artists.join(", "), true);
+    }
+    if let Some(rating) = post.rating {
+        embed.field("Rating", rating.name(), true);
+    }
+    embed.field("Score", post.score, true);
+
+    if !post.tags.is_empty() {
+        let mut tags = String::new();
+        for tag in &post.tags {
+            let item = format!("`{}` ", tag);
+            if tags.len() + item.len() > FIELD_VALUE_LIMIT {
+                break;
+            }
+            tags.push_str(&item);
+        }
+
+        embed.field("Tags", tags, false);
+    }
+
+    if let Some(source) = post.source.as_ref().filter(|source| !source.is_empty()) {
+        embed.field("Source", source, false);
+    }
+
+    embed
+}
+
+/// Splits a space separated tag string, as most sites return them
+fn split_tags(tags: &str) -> Vec<String> {
+    tags.split_whitespace().map(|tag| tag.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(tags: &[&str], rating: Option<Rating>) -> PostQuery {
+        PostQuery {
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            rating,
+            random: false,
+            limit: SEARCH_LIMIT,
+        }
+    }
+
+    #[test]
+    fn limits_the_tags_of_danbooru_searches() {
+        let danbooru = Danbooru::new(HttpClient::new());
+
+        assert!(check_tag_count(&danbooru, &query(&["cat_ears", "smile"], None)).is_ok());
+        assert!(check_tag_count(&danbooru, &query(&["cat_ears"], Some(Rating::Safe))).is_ok());
+
+        let error = check_tag_count(
+            &danbooru,
+            &query(&["cat_ears", "smile"], Some(Rating::Safe)),
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "danbooru only allows searching for 2 tags at once, and a rating counts as one"
+        );
+    }
+
+    #[test]
+    fn leaves_other_sites_unlimited() {
+        let gelbooru = Gelbooru::new(HttpClient::new());
+        let tags = ["a", "b", "c", "d", "e"];
+
+        assert!(check_tag_count(&gelbooru, &query(&tags, Some(Rating::Explicit))).is_ok());
+    }
+}
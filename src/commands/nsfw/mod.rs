@@ -1,9 +1,14 @@
 use serenity::framework::standard::macros::group;
 
+mod booru;
 mod nhentai;
 
+pub use self::booru::{BooruRegistry, BooruRegistryContainer};
+pub use self::nhentai::detect_gallery_links;
+
+use self::booru::BOORU_COMMAND;
 use self::nhentai::NHENTAI_COMMAND;
 
 #[group]
-#[commands(nhentai)]
+#[commands(nhentai, booru)]
 struct NSFW;
@@ -0,0 +1,163 @@
+use crate::core::http::{HttpClient, Service};
+use serde::Deserialize;
+use serde_aux::field_attributes::deserialize_number_from_string;
+
+const API_URL: &str = "https://nhentai.net/api";
+
+pub async fn fetch_gallery(client: &HttpClient, id: &str) -> reqwest::Result<GalleryResponse> {
+    let request = client.get(format!("{}/gallery/{}", API_URL, id).as_str());
+
+    client.send(Service::Nhentai, request).await?.json().await
+}
+
+/// Searches galleries with nhentai's query syntax, returning one page of results
+pub async fn search_galleries(
+    client: &HttpClient,
+    query: &str,
+    sort: SortOrder,
+    page: u32,
+) -> reqwest::Result<SearchResponse> {
+    let mut params = vec![("query", query.to_string()), ("page", page.to_string())];
+    if let Some(sort) = sort.as_param() {
+        params.push(("sort", sort.to_string()));
+    }
+
+    let request = client
+        .get(format!("{}/galleries/search", API_URL).as_str())
+        .query(&params);
+
+    client
+        .send(Service::Nhentai, request)
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+}
+
+pub fn get_cover_url(media_id: &str, cover_ext_raw: &str) -> String {
+    let cover_ext = parse_extension(cover_ext_raw);
+
+    format!(
+        "https://t.nhentai.net/galleries/{}/cover.{}",
+        media_id, cover_ext
+    )
+}
+
+/// URL of a page's full size image. Page numbers start at 1.
+pub fn get_page_url(media_id: &str, page_number: usize, page_ext_raw: &str) -> String {
+    let page_ext = parse_extension(page_ext_raw);
+
+    format!(
+        "https://i.nhentai.net/galleries/{}/{}.{}",
+        media_id, page_number, page_ext
+    )
+}
+
+fn parse_extension(raw_ext: &str) -> String {
+    match raw_ext {
+        "j" => "jpg",
+        "p" => "png",
+        "g" => "gif",
+        _ => "",
+    }
+    .to_string()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortOrder {
+    Recent,
+    Popular,
+    PopularToday,
+    PopularWeek,
+}
+
+impl SortOrder {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "recent" | "date" => Some(SortOrder::Recent),
+            "popular" => Some(SortOrder::Popular),
+            "popular-today" | "today" => Some(SortOrder::PopularToday),
+            "popular-week" | "week" => Some(SortOrder::PopularWeek),
+            _ => None,
+        }
+    }
+
+    // Recent is the API's default order
+    fn as_param(self) -> Option<&'static str> {
+        match self {
+            SortOrder::Recent => None,
+            SortOrder::Popular => Some("popular"),
+            SortOrder::PopularToday => Some("popular-today"),
+            SortOrder::PopularWeek => Some("popular-week"),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SearchResponse {
+    pub result: Vec<GalleryResponse>,
+    pub num_pages: i32,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GalleryResponse {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub id: i32,
+    pub media_id: String,
+    pub title: GalleryTitle,
+    pub images: GalleryImages,
+    pub tags: Vec<GalleryTagInfo>,
+    pub num_pages: i32,
+    pub num_favorites: i32,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GalleryTitle {
+    pub pretty: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GalleryImages {
+    pub pages: Vec<GalleryImageInfo>,
+    pub cover: GalleryImageInfo,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GalleryTagInfo {
+    #[serde(rename = "type")]
+    pub tag_type: TagType,
+
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GalleryImageInfo {
+    pub t: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TagType {
+    Parody,
+    Tag,
+    Artist,
+    Group,
+    Language,
+    Category,
+    Character,
+}
+
+impl TagType {
+    /// Name of the type as used in search queries and tag blacklists
+    pub fn name(&self) -> &'static str {
+        match self {
+            TagType::Parody => "parody",
+            TagType::Tag => "tag",
+            TagType::Artist => "artist",
+            TagType::Group => "group",
+            TagType::Language => "language",
+            TagType::Category => "category",
+            TagType::Character => "character",
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use super::api::fetch_gallery;
+use super::blacklist::guild_blacklist;
+use super::gallery_embed;
+use crate::core::{
+    checks::ISADMIN_CHECK,
+    config::get_config,
+    consts::MAIN_COLOR,
+    http::get_http_client,
+    settings::{get_settings_store, guild_settings},
+};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serenity::{
+    client::Context,
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    model::channel::Message,
+};
+use std::collections::HashSet;
+
+// How many galleries are looked up per message at most
+const MAX_GALLERIES_PER_MESSAGE: usize = 3;
+
+lazy_static! {
+    static ref LINK_REGEX: Regex = Regex::new(r"nhentai\.net/g/([0-9]{1,6})").unwrap();
+    // Six digits standing on their own, not part of a longer number or word
+    static ref CODE_REGEX: Regex = Regex::new(r"(?:^|\s)([0-9]{6})(?:[\s.,!?]|$)").unwrap();
+}
+
+/// Replies with the info cards of the galleries linked in a message, if the guild enabled it.
+/// Only ever happens in nsfw channels.
+pub async fn detect_gallery_links(ctx: &Context, msg: &Message) -> CommandResult {
+    let guild_id = match msg.guild_id {
+        Some(guild_id) => guild_id,
+        None => return Ok(()),
+    };
+
+    if msg.author.bot {
+        return Ok(());
+    }
+
+    // Commands handle their own ids
+    let config = get_config(ctx).await;
+    if config
+        .prefixes
+        .iter()
+        .any(|prefix| msg.content.starts_with(prefix.as_str()))
+    {
+        return Ok(());
+    }
+
+    let settings = guild_settings(ctx, guild_id).await;
+    if !settings.nhentai_links {
+        return Ok(());
+    }
+
+    let mut ids: Vec<&str> = LINK_REGEX
+        .captures_iter(&msg.content)
+        .map(|captures| captures.get(1).unwrap().as_str())
+        .collect();
+    if settings.nhentai_codes {
+        ids.extend(
+            CODE_REGEX
+                .captures_iter(&msg.content)
+                .map(|captures| captures.get(1).unwrap().as_str()),
+        );
+    }
+    let mut seen = HashSet::new();
+    ids.retain(|id| seen.insert(*id));
+
+    if ids.is_empty() || !msg.channel_id.to_channel(ctx).await?.is_nsfw() {
+        return Ok(());
+    }
+
+    let client = get_http_client(ctx).await;
+    let blacklist = guild_blacklist(ctx, msg.guild_id).await;
+
+    for id in ids.into_iter().take(MAX_GALLERIES_PER_MESSAGE) {
+        // Bare codes are often something else entirely, so missing galleries are skipped silently
+        let data = match fetch_gallery(&client, id).await {
+            Ok(data) => data,
+            Err(_) => continue,
+        };
+        let embed = gallery_embed(&data, &blacklist);
+
+        let _ = msg
+            .channel_id
+            .send_message(&ctx.http, |m| {
+                m.reference_message(msg).embed(|e| {
+                    *e = embed;
+                    e
+                })
+            })
+            .await;
+    }
+
+    Ok(())
+}
+
+#[command]
+#[only_in(guilds)]
+#[checks(IsAdmin)]
+#[description(
+    "Enables or disables replying to nhentai links posted in nsfw channels with their info card. \
+    With `codes`, bare six-digit codes are recognized too. Shows the current setting without arguments."
+)]
+#[usage("[links | codes] [on | off]")]
+#[example("links on")]
+#[example("codes off")]
+pub async fn autodetect(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap();
+
+    let kind = args.single::<String>().ok();
+    let enabled = match args.single::<String>().ok().as_deref() {
+        Some("on") => Some(true),
+        Some("off") => Some(false),
+        Some(_) => return Err(CommandError::from("The argument must be either on or off")),
+        None => None,
+    };
+
+    if let Some(kind) = &kind {
+        if kind != "links" && kind != "codes" {
+            return Err(CommandError::from(
+                "The setting must be either links or codes",
+            ));
+        }
+    }
+
+    let settings = match (kind.as_deref(), enabled) {
+        (Some(kind), Some(enabled)) => {
+            let store = get_settings_store(ctx).await;
+            let mut store = store.write().await;
+
+            store.update(|settings| {
+                let guild = settings.guild_mut(guild_id);
+                match kind {
+                    "links" => guild.nhentai_links = enabled,
+                    _ => guild.nhentai_codes = enabled,
+                }
+
+                guild.clone()
+            })?
+        }
+        _ => guild_settings(ctx, guild_id).await,
+    };
+
+    let describe = |enabled: bool| match enabled {
+        true => "on",
+        false => "off",
+    };
+
+    let _ = msg
+        .channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.colour(MAIN_COLOR)
+                    .title("nhentai auto-detection")
+                    .description(
+                        "Only happens in nsfw channels. Codes are only recognized while links are on.",
+                    )
+                    .field("Links", describe(settings.nhentai_links), true)
+                    .field("Codes", describe(settings.nhentai_codes), true)
+            })
+        })
+        .await;
+
+    Ok(())
+}
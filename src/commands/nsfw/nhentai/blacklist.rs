@@ -0,0 +1,156 @@
+use super::api::GalleryTagInfo;
+use crate::core::{
+    checks::ISADMIN_CHECK,
+    consts::MAIN_COLOR,
+    settings::{get_settings_store, guild_settings},
+};
+use serenity::{
+    client::Context,
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    model::{channel::Message, id::GuildId},
+};
+
+#[command]
+#[only_in(guilds)]
+#[checks(IsAdmin)]
+#[description(
+    "Manages the tags whose galleries get their cover hidden on this server. \
+    Tags can be limited to a type, like `artist:name`, and several are separated by commas. \
+    Lists the blacklisted tags without arguments."
+)]
+#[usage("[add | remove] <tags>")]
+#[example("add gore, artist:someone")]
+#[example("remove gore")]
+pub async fn blacklist(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let guild_id = msg.guild_id.unwrap();
+
+    let action = args.single::<String>().ok();
+    let tags: Vec<String> = args
+        .rest()
+        .split(',')
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+
+    let blacklist = match action.as_deref() {
+        None => guild_settings(ctx, guild_id).await.blacklisted_tags,
+        Some(action @ "add") | Some(action @ "remove") => {
+            if tags.is_empty() {
+                return Err(CommandError::from("You need to name the tags"));
+            }
+
+            let store = get_settings_store(ctx).await;
+            let mut store = store.write().await;
+
+            store.update(|settings| {
+                let blacklist = &mut settings.guild_mut(guild_id).blacklisted_tags;
+
+                match action {
+                    "add" => {
+                        for tag in tags {
+                            if !blacklist.contains(&tag) {
+                                blacklist.push(tag);
+                            }
+                        }
+                    }
+                    _ => blacklist.retain(|tag| !tags.contains(tag)),
+                }
+
+                blacklist.clone()
+            })?
+        }
+        Some(_) => {
+            return Err(CommandError::from(
+                "The action must be either add or remove",
+            ))
+        }
+    };
+
+    let _ = msg
+        .channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.colour(MAIN_COLOR).title("Blacklisted tags").description(
+                    match blacklist.is_empty() {
+                        true => "No tags are blacklisted on this server.".to_string(),
+                        false => blacklist
+                            .iter()
+                            .map(|tag| format!("`{}`", tag))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    },
+                )
+            })
+        })
+        .await;
+
+    Ok(())
+}
+
+/// The blacklisted tags of the guild the message was sent in. Nothing is blacklisted in DMs.
+pub async fn guild_blacklist(ctx: &Context, guild_id: Option<GuildId>) -> Vec<String> {
+    match guild_id {
+        Some(guild_id) => guild_settings(ctx, guild_id).await.blacklisted_tags,
+        None => Vec::new(),
+    }
+}
+
+/// Names of the gallery's tags which are on the blacklist
+pub fn find_blacklisted_tags<'a>(tags: &'a [GalleryTagInfo], blacklist: &[String]) -> Vec<&'a str> {
+    tags.iter()
+        .filter(|tag| {
+            let name = tag.name.to_lowercase();
+
+            blacklist.iter().any(|entry| {
+                let entry = entry.to_lowercase();
+
+                match entry.split_once(':') {
+                    Some((tag_type, entry_name)) => {
+                        tag_type == tag.tag_type.name() && entry_name == name
+                    }
+                    None => entry == name,
+                }
+            })
+        })
+        .map(|tag| tag.name.as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::fixture;
+
+    fn blacklisted(entries: &[&str]) -> Vec<String> {
+        let tags: Vec<GalleryTagInfo> =
+            serde_json::from_str(&fixture("nhentai/tags.json")).unwrap();
+        let blacklist: Vec<String> = entries.iter().map(|entry| entry.to_string()).collect();
+
+        find_blacklisted_tags(&tags, &blacklist)
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn matches_typed_entries_by_type_and_name() {
+        assert_eq!(blacklisted(&["artist:shindol"]), vec!["shindol"]);
+        assert!(blacklisted(&["tag:shindol", "parody:doujinshi"]).is_empty());
+    }
+
+    #[test]
+    fn matches_untyped_entries_of_any_type() {
+        assert_eq!(
+            blacklisted(&["shindol", "lolicon", "unknown"]),
+            vec!["lolicon", "shindol"]
+        );
+    }
+
+    #[test]
+    fn ignores_case() {
+        assert_eq!(
+            blacklisted(&["full color", "Language:Japanese"]),
+            vec!["Full Color", "japanese"]
+        );
+    }
+}
@@ -0,0 +1,158 @@
+mod api;
+mod autodetect;
+mod blacklist;
+mod reader;
+mod search;
+
+use lazy_static::lazy_static;
+
+pub use self::autodetect::detect_gallery_links;
+
+use self::api::{fetch_gallery, get_cover_url, GalleryResponse, GalleryTagInfo, TagType};
+use self::autodetect::AUTODETECT_COMMAND;
+use self::blacklist::{find_blacklisted_tags, guild_blacklist, BLACKLIST_COMMAND};
+use self::reader::READ_COMMAND;
+use self::search::SEARCH_COMMAND;
+use crate::core::{checks::ISNSFW_CHECK, http::get_http_client};
+use regex::{Captures, Regex};
+use serenity::{
+    builder::CreateEmbed,
+    client::Context,
+    framework::standard::{macros::command, Args, CommandResult},
+    model::channel::Message,
+};
+
+// nhentai's brand color, used for all gallery embeds
+const NHENTAI_COLOR: u32 = 0xEC2854;
+
+lazy_static! {
+    // Regex to parse nhentai IDs from command input
+    static ref ID_REGEX: Regex = Regex::new(r"([0-9]{1,6})(?:,|\s|$)+").unwrap();
+}
+
+#[command]
+#[aliases("nh")]
+#[description(
+    "Looks up one or multiple nhentai IDs and returns information about the associated doujinshi."
+)]
+#[checks("IsNSFW")]
+#[sub_commands(search, read, blacklist, autodetect)]
+pub async fn nhentai(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let ids_raw = args.message();
+    let id_captures: Vec<Captures> = ID_REGEX.captures_iter(ids_raw).collect();
+    let client = get_http_client(ctx).await;
+    let blacklist = guild_blacklist(ctx, msg.guild_id).await;
+
+    for id_capture in id_captures {
+        let id = id_capture.get(1).unwrap().as_str();
+
+        let data = fetch_gallery(&client, id).await?;
+        let embed = gallery_embed(&data, &blacklist);
+
+        let _ = msg
+            .channel_id
+            .send_message(&ctx.http, |m| {
+                m.embed(|e| {
+                    *e = embed;
+                    e
+                })
+            })
+            .await;
+    }
+
+    Ok(())
+}
+
+/// Builds the info card of a gallery, with its cover, tags and stats.
+/// If the gallery has blacklisted tags, the cover is only linked behind a spoiler, with a warning.
+fn gallery_embed(data: &GalleryResponse, blacklist: &[String]) -> CreateEmbed {
+    let cover_url = get_cover_url(&data.media_id, &data.images.cover.t);
+    let blacklisted_tags = find_blacklisted_tags(&data.tags, blacklist);
+
+    let mut embed = CreateEmbed::default();
+    embed
+        .color(NHENTAI_COLOR)
+        .title(&data.title.pretty)
+        .url(format!("https://nhentai.net/g/{}", data.id));
+
+    match blacklisted_tags.is_empty() {
+        true => embed.thumbnail(cover_url),
+        false => embed.field(
+            "⚠️ Blacklisted tags",
+            format!(
+                "This gallery has tags blacklisted on this server: {}\n||[Cover]({})||",
+                blacklisted_tags
+                    .iter()
+                    .map(|tag| format!("`{}`", tag))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                cover_url
+            ),
+            false,
+        ),
+    };
+
+    embed.fields(vec![
+        ("Tags", build_tag_string(&data.tags), false),
+        (
+            "Stats",
+            format!(
+                "**{pages}** pages, **{favorites}** favorites",
+                pages = data.num_pages,
+                favorites = data.num_favorites
+            ),
+            false,
+        ),
+    ]);
+
+    embed
+}
+
+fn build_tag_string(all_tags: &[GalleryTagInfo]) -> String {
+    let tags = filter_tags_by_type(all_tags, TagType::Tag);
+    let parodies = filter_tags_by_type(all_tags, TagType::Parody);
+    let characters = filter_tags_by_type(all_tags, TagType::Character);
+    let artists = filter_tags_by_type(all_tags, TagType::Artist);
+    let groups = filter_tags_by_type(all_tags, TagType::Group);
+    let languages = filter_tags_by_type(all_tags, TagType::Language);
+    let categories = filter_tags_by_type(all_tags, TagType::Category);
+
+    let mut tags_string = format_tag_items(&tags) + "\n";
+
+    if !parodies.is_empty() {
+        tags_string += format!("\n Parodies: {}", format_tag_items(&parodies)).as_str();
+    }
+
+    if !characters.is_empty() {
+        tags_string += format!("\n Characters: {}", format_tag_items(&characters)).as_str();
+    }
+
+    if !artists.is_empty() {
+        tags_string += format!("\n Artists: {}", format_tag_items(&artists)).as_str();
+    }
+
+    if !groups.is_empty() {
+        tags_string += format!("\n Groups: {}", format_tag_items(&groups)).as_str();
+    }
+
+    if !languages.is_empty() {
+        tags_string += format!("\n Languages: {}", format_tag_items(&languages)).as_str();
+    }
+
+    if !categories.is_empty() {
+        tags_string += format!("\n Categories: {}", format_tag_items(&categories)).as_str();
+    }
+
+    tags_string
+}
+
+fn filter_tags_by_type(tags: &[GalleryTagInfo], tag_type: TagType) -> Vec<&GalleryTagInfo> {
+    tags.iter().filter(|t| t.tag_type == tag_type).collect()
+}
+
+fn format_tag_items(tags: &Vec<&GalleryTagInfo>) -> String {
+    tags.iter()
+        .map(|tag| format!("`{}`", tag.name))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
@@ -0,0 +1,68 @@
+use super::api::{fetch_gallery, get_page_url};
+use super::blacklist::{find_blacklisted_tags, guild_blacklist};
+use super::{ID_REGEX, NHENTAI_COLOR};
+use crate::core::{
+    checks::ISNSFW_CHECK, http::get_http_client, pagination::paginator::send_reader,
+};
+use serenity::{
+    builder::CreateEmbed,
+    client::Context,
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    model::channel::Message,
+};
+
+#[command]
+#[aliases("reader")]
+#[checks("IsNSFW")]
+#[min_args(1)]
+#[max_args(2)]
+#[description(
+    "Reads a doujinshi page by page. Turn the pages with the reactions, \
+    or jump to a page by sending just its number."
+)]
+#[usage("<id> [page]")]
+#[example("177013 5")]
+pub async fn read(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let id_arg = args.single::<String>()?;
+    let id = match ID_REGEX.captures(&id_arg) {
+        Some(captures) => captures.get(1).unwrap().as_str().to_string(),
+        None => return Err(CommandError::from("That's not a valid nhentai ID")),
+    };
+    let start_page = args.single::<usize>().unwrap_or(1).max(1);
+
+    let client = get_http_client(ctx).await;
+    let data = fetch_gallery(&client, &id).await?;
+
+    // The pages can't be hidden like the cover, so blacklisted galleries can't be read at all
+    let blacklist = guild_blacklist(ctx, msg.guild_id).await;
+    let blacklisted_tags = find_blacklisted_tags(&data.tags, &blacklist);
+    if !blacklisted_tags.is_empty() {
+        return Err(CommandError::from(format!(
+            "This gallery has tags blacklisted on this server: {}",
+            blacklisted_tags.join(", ")
+        )));
+    }
+
+    let gallery_url = format!("https://nhentai.net/g/{}", data.id);
+    let pages: Vec<CreateEmbed> = data
+        .images
+        .pages
+        .iter()
+        .enumerate()
+        .map(|(i, page)| {
+            let mut embed = CreateEmbed::default();
+            embed
+                .color(NHENTAI_COLOR)
+                .title(&data.title.pretty)
+                .url(&gallery_url)
+                .image(get_page_url(&data.media_id, i + 1, &page.t));
+            embed
+        })
+        .collect();
+
+    if pages.is_empty() {
+        return Err(CommandError::from("That gallery has no pages"));
+    }
+
+    send_reader(ctx, msg.channel_id, msg.author.id, pages, start_page - 1).await
+}
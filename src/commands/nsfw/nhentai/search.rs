@@ -0,0 +1,222 @@
+use super::api::{search_galleries, SortOrder};
+use super::blacklist::guild_blacklist;
+use super::gallery_embed;
+use crate::core::{
+    checks::ISNSFW_CHECK, http::get_http_client, pagination::paginator::send_paginated,
+};
+use serenity::{
+    builder::CreateEmbed,
+    client::Context,
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    model::channel::Message,
+};
+
+// Namespaces nhentai's search understands in front of a tag, like "artist:name"
+const NAMESPACES: [&str; 7] = [
+    "tag",
+    "artist",
+    "parody",
+    "character",
+    "group",
+    "language",
+    "category",
+];
+
+#[command]
+#[checks("IsNSFW")]
+#[min_args(1)]
+#[description(
+    "Searches nhentai. Tags can be searched with `tag:`, `artist:`, `parody:`, `character:`, \
+    `group:`, `language:` and `category:`, values with spaces need quotes. \
+    Prefix a term with `-` to exclude it. Results are sorted by `sort:recent` (default), \
+    `sort:popular`, `sort:popular-week` or `sort:popular-today`, and `page:<n>` shows later results."
+)]
+#[usage("<query>")]
+#[example("tag:\"full color\" language:english -tag:yaoi sort:popular")]
+pub async fn search(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let query = parse_query(args.rest())?;
+
+    if query.terms.is_empty() {
+        return Err(CommandError::from("You need to search for something"));
+    }
+
+    let client = get_http_client(ctx).await;
+    let blacklist = guild_blacklist(ctx, msg.guild_id).await;
+    let results = search_galleries(&client, &query.terms.join(" "), query.sort, query.page).await?;
+
+    if results.result.is_empty() {
+        let _ = msg
+            .channel_id
+            .send_message(&ctx.http, |m| {
+                m.content(format!(
+                    "Could not find any galleries matching '{}'",
+                    query.terms.join(" ")
+                ))
+            })
+            .await;
+
+        return Ok(());
+    }
+
+    let pages: Vec<CreateEmbed> = results
+        .result
+        .iter()
+        .map(|gallery| {
+            let mut embed = gallery_embed(gallery, &blacklist);
+            embed.author(|a| {
+                a.name(format!(
+                    "Results page {} of {}",
+                    query.page, results.num_pages
+                ))
+            });
+            embed
+        })
+        .collect();
+
+    send_paginated(ctx, msg.channel_id, msg.author.id, pages).await
+}
+
+struct SearchQuery {
+    // Terms in nhentai's syntax, like `tag:"full color"` or `-artist:name`
+    terms: Vec<String>,
+    sort: SortOrder,
+    page: u32,
+}
+
+/// Turns the user's query into the one nhentai expects, taking out the sort and page options
+fn parse_query(input: &str) -> Result<SearchQuery, CommandError> {
+    let mut query = SearchQuery {
+        terms: Vec::new(),
+        sort: SortOrder::Recent,
+        page: 1,
+    };
+
+    for token in tokenize(input) {
+        let (excluded, term) = match token.strip_prefix('-') {
+            Some(term) => (true, term),
+            None => (false, token.as_str()),
+        };
+
+        let term = match term.split_once(':') {
+            Some(("sort", order)) if !excluded => {
+                query.sort = SortOrder::from_name(order).ok_or_else(|| {
+                    CommandError::from(format!(
+                        "Unknown sort order '{}', use recent, popular, popular-week or popular-today",
+                        order
+                    ))
+                })?;
+                continue;
+            }
+            Some(("page", page)) if !excluded => {
+                query.page = match page.parse() {
+                    Ok(page) if page > 0 => page,
+                    _ => return Err(CommandError::from("The page needs to be a positive number")),
+                };
+                continue;
+            }
+            Some((namespace, value)) if is_namespace(namespace) => {
+                format!("{}:\"{}\"", namespace.to_lowercase(), value)
+            }
+            // Anything else is searched as a phrase, like titles with a colon or several words
+            _ if term.contains(|c: char| c == ':' || c.is_whitespace()) => {
+                format!("\"{}\"", term)
+            }
+            _ => term.to_string(),
+        };
+
+        query.terms.push(match excluded {
+            true => format!("-{}", term),
+            false => term,
+        });
+    }
+
+    Ok(query)
+}
+
+fn is_namespace(name: &str) -> bool {
+    NAMESPACES.contains(&name.to_lowercase().as_str())
+}
+
+// Splits on whitespace, except inside double quotes. The quotes themselves are dropped.
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(input: &str) -> Vec<String> {
+        parse_query(input).unwrap().terms
+    }
+
+    #[test]
+    fn keeps_quoted_phrases_together() {
+        assert_eq!(
+            tokenize("  \"full color\" english  big\"ger fish\" "),
+            vec!["full color", "english", "bigger fish"]
+        );
+    }
+
+    #[test]
+    fn quotes_tag_values() {
+        assert_eq!(
+            terms("tag:\"full color\" Artist:name -tag:yaoi"),
+            vec!["tag:\"full color\"", "artist:\"name\"", "-tag:\"yaoi\""]
+        );
+    }
+
+    #[test]
+    fn searches_titles_with_a_colon_as_phrases() {
+        assert_eq!(
+            terms("re:zero \"steins;gate 0\" -\"fate:zero\" plain"),
+            vec![
+                "\"re:zero\"",
+                "\"steins;gate 0\"",
+                "-\"fate:zero\"",
+                "plain"
+            ]
+        );
+    }
+
+    #[test]
+    fn takes_out_the_sort_order_and_page() {
+        let query = parse_query("sort:popular-week page:3 english").unwrap();
+
+        assert_eq!(query.terms, vec!["english"]);
+        assert_eq!(query.sort, SortOrder::PopularWeek);
+        assert_eq!(query.page, 3);
+
+        let query = parse_query("english").unwrap();
+        assert_eq!(query.sort, SortOrder::Recent);
+        assert_eq!(query.page, 1);
+    }
+
+    #[test]
+    fn rejects_bad_options() {
+        assert!(parse_query("english page:0").is_err());
+        assert!(parse_query("english page:two").is_err());
+        assert!(parse_query("english page:-1").is_err());
+        assert!(parse_query("english sort:oldest").is_err());
+    }
+}
@@ -14,7 +14,7 @@ use serenity::{
     prelude::Context,
 };
 
-const BOT_VERSION: &'static str = env!("CARGO_PKG_VERSION");
+const BOT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[command]
 pub async fn info(ctx: &Context, msg: &Message, _: Args) -> CommandResult {
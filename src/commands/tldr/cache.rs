@@ -0,0 +1,293 @@
+use super::platforms::page_paths;
+use crate::core::{
+    fuzzy,
+    http::{HttpClient, Service},
+};
+use log::{info, warn};
+use serenity::prelude::*;
+use std::{
+    error::Error,
+    fs,
+    io::{self, Cursor},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use zip::ZipArchive;
+
+// After a failed sync, try again after this many minutes instead of waiting for the next interval
+const RETRY_INTERVAL_MINS: u64 = 30;
+
+// File inside the cache directory whose modification time marks the last successful sync
+const SYNC_MARKER: &str = ".synced";
+
+pub struct TldrCacheContainer;
+impl TypeMapKey for TldrCacheContainer {
+    type Value = Arc<TldrCache>;
+}
+
+/// Local copy of the tldr-pages archive, so lookups don't need a request to GitHub each time
+/// and keep working while it's unreachable
+pub struct TldrCache {
+    client: HttpClient,
+    archive_url: String,
+    dir: PathBuf,
+    // Index of all cached pages. Also held for reading while pages are read,
+    // and for writing while a sync replaces the pages.
+    index: RwLock<Vec<PageEntry>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PageEntry {
+    pub name: String,
+    pub platform: String,
+    // The one line summary of the page
+    pub description: String,
+}
+
+impl TldrCache {
+    /// Opens the cache, indexing the pages downloaded by previous runs
+    pub fn new(client: HttpClient, data_dir: &Path, archive_url: String) -> Self {
+        let dir = data_dir.join("tldr");
+        let index = build_index(&dir);
+
+        TldrCache {
+            client,
+            archive_url,
+            dir,
+            index: RwLock::new(index),
+        }
+    }
+
+    /// Reads the page with the given name, looking in the platform's pages first
+    /// and in the ones common to all platforms second. Translated pages are preferred
+    /// if a language is given, with the English ones as fallback. Returns None if it doesn't exist.
+    pub async fn page(&self, platform: &str, language: Option<&str>, name: &str) -> Option<String> {
+        // Page names and languages come from user input, which mustn't be able to point outside the cache
+        let is_unsafe = |part: &str| part.contains(['/', '\\']) || part.starts_with('.');
+        if is_unsafe(name) || language.is_some_and(is_unsafe) {
+            return None;
+        }
+
+        let _index = self.index.read().await;
+
+        page_paths(platform, language, name)
+            .iter()
+            .find_map(|path| fs::read_to_string(self.dir.join(path)).ok())
+    }
+
+    /// Platforms which have an English page with the given name
+    pub async fn platforms(&self, name: &str) -> Vec<String> {
+        let index = self.index.read().await;
+
+        index
+            .iter()
+            .filter(|page| page.name == name)
+            .map(|page| page.platform.clone())
+            .collect()
+    }
+
+    /// Codes of the languages pages have been translated into, like "de" or "pt_BR"
+    pub async fn languages(&self) -> Vec<String> {
+        let _index = self.index.read().await;
+
+        let mut languages: Vec<String> = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    name.strip_prefix("pages.")
+                        .map(|language| language.to_string())
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        languages.sort();
+        languages
+    }
+
+    /// Finds the pages whose name or description contains the keyword, the ones matching by name first
+    pub async fn search(&self, keyword: &str) -> Vec<PageEntry> {
+        let keyword = keyword.to_lowercase();
+        let index = self.index.read().await;
+
+        let (mut by_name, by_description): (Vec<PageEntry>, Vec<PageEntry>) = index
+            .iter()
+            .filter(|page| {
+                page.name.contains(&keyword) || page.description.to_lowercase().contains(&keyword)
+            })
+            .cloned()
+            .partition(|page| page.name.contains(&keyword));
+
+        by_name.extend(by_description);
+        by_name
+    }
+
+    /// Names of the pages closest to the given, misspelled one
+    pub async fn suggest(&self, name: &str) -> Vec<String> {
+        let index = self.index.read().await;
+
+        // Pages with the same name exist for several platforms, but should only be suggested once
+        let mut names: Vec<&str> = index.iter().map(|page| page.name.as_str()).collect();
+        names.dedup();
+
+        fuzzy::suggest(name, names, 5)
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect()
+    }
+
+    /// Whether the archive has been downloaded at least once
+    pub fn is_synced(&self) -> bool {
+        self.last_sync().is_some()
+    }
+
+    fn last_sync(&self) -> Option<SystemTime> {
+        fs::metadata(self.dir.join(SYNC_MARKER))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    /// Downloads the latest archive and replaces the cached pages with it
+    pub async fn sync(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let response = self
+            .client
+            .send(Service::TldrArchive, self.client.get(&self.archive_url))
+            .await?
+            .error_for_status()?;
+        let archive = response.bytes().await?;
+
+        // Extract next to the current pages first, so a failed extraction leaves them intact
+        let staging_dir = self.dir.with_extension("tmp");
+        let extract_dir = staging_dir.clone();
+        let new_index = tokio::task::spawn_blocking(move || {
+            extract_pages(&archive, &extract_dir).map(|_| build_index(&extract_dir))
+        })
+        .await??;
+
+        let mut index = self.index.write().await;
+
+        if self.dir.exists() {
+            fs::remove_dir_all(&self.dir)?;
+        }
+        fs::rename(&staging_dir, &self.dir)?;
+
+        *index = new_index;
+
+        Ok(())
+    }
+}
+
+/// Extracts the pages of all platforms and languages from the archive into the given directory
+fn extract_pages(archive: &[u8], dir: &Path) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut archive = ZipArchive::new(Cursor::new(archive))?;
+
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+
+        // Skips entries with paths that would end up outside the directory
+        let path = match file.enclosed_name() {
+            Some(path) if path.starts_with("pages") || is_translated_pages(path) => dir.join(path),
+            _ => continue,
+        };
+
+        if file.is_dir() {
+            continue;
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        io::copy(&mut file, &mut fs::File::create(&path)?)?;
+    }
+
+    fs::File::create(dir.join(SYNC_MARKER))?;
+
+    Ok(())
+}
+
+/// Lists the english pages of all platforms, sorted by name
+fn build_index(dir: &Path) -> Vec<PageEntry> {
+    let mut index = Vec::new();
+
+    let platforms = match fs::read_dir(dir.join("pages")) {
+        Ok(platforms) => platforms,
+        Err(_) => return index,
+    };
+
+    for platform in platforms.filter_map(Result::ok) {
+        let platform_name = platform.file_name().to_string_lossy().to_string();
+        let pages = match fs::read_dir(platform.path()) {
+            Ok(pages) => pages,
+            Err(_) => continue,
+        };
+
+        for page in pages.filter_map(Result::ok) {
+            let path = page.path();
+            let name = match path.file_stem() {
+                Some(name) => name.to_string_lossy().to_string(),
+                None => continue,
+            };
+
+            // The first "> " line is the summary of the page
+            let description = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| {
+                    content
+                        .lines()
+                        .find(|line| line.starts_with('>'))
+                        .map(|line| line.trim_start_matches('>').trim().to_string())
+                })
+                .unwrap_or_default();
+
+            index.push(PageEntry {
+                name,
+                platform: platform_name.clone(),
+                description,
+            });
+        }
+    }
+
+    index.sort_by(|a, b| a.name.cmp(&b.name).then(a.platform.cmp(&b.platform)));
+    index
+}
+
+// Translated pages are in directories like "pages.de"
+fn is_translated_pages(path: &Path) -> bool {
+    path.components()
+        .next()
+        .and_then(|component| component.as_os_str().to_str())
+        .is_some_and(|dir| dir.starts_with("pages."))
+}
+
+/// Keeps the cache up to date by downloading the archive whenever it's older than the given interval
+pub async fn run_tldr_sync(cache: Arc<TldrCache>, interval_hours: u64) {
+    let interval = Duration::from_secs(interval_hours * 60 * 60);
+
+    loop {
+        let age = cache
+            .last_sync()
+            .and_then(|last_sync| last_sync.elapsed().ok());
+
+        let wait = match age {
+            Some(age) if age < interval => interval - age,
+            _ => match cache.sync().await {
+                Ok(()) => {
+                    info!("Synced the tldr pages");
+                    interval
+                }
+                Err(why) => {
+                    warn!("Failed to sync the tldr pages: {}", why);
+                    Duration::from_secs(RETRY_INTERVAL_MINS * 60)
+                }
+            },
+        };
+
+        tokio::time::sleep(wait).await;
+    }
+}
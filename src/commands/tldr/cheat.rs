@@ -0,0 +1,72 @@
+use crate::core::{
+    config::get_config,
+    http::{get_http_client, Service},
+    pagination::{page_builders::code_pages, paginator::send_paginated},
+};
+use reqwest::{StatusCode, Url};
+use serenity::{
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    model::channel::Message,
+    prelude::Context,
+};
+
+#[command]
+#[min_args(1)]
+#[description(
+    "Shows the cheat sheet of a command from cheat.sh. Programming languages can be asked questions, \
+    like `python/reverse a list`."
+)]
+#[usage("<topic> [question]")]
+#[example("tar")]
+#[example("rust/read a file")]
+pub async fn cheat(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let query = args.rest().trim();
+
+    // The url is checked when the config is loaded
+    let mut url = Url::parse(&get_config(ctx).await.tldr.cheat_sh_url)?;
+
+    // cheat.sh takes questions as "topic/words+of+the+question"
+    url.path_segments_mut()
+        .map_err(|_| "The cheat.sh url can't have a path")?
+        .pop_if_empty()
+        .extend(
+            query
+                .split('/')
+                .map(|segment| segment.trim().replace(' ', "+")),
+        );
+    // Plain text, without the color codes meant for terminals
+    url.set_query(Some("T"));
+
+    let client = get_http_client(ctx).await;
+    let resp = client.send(Service::Manuals, client.get(url)).await?;
+    let text = match resp.status() {
+        StatusCode::OK => resp.text().await?,
+        StatusCode::NOT_FOUND => String::new(),
+        s => {
+            return Err(CommandError::from(format!(
+                "Unexpected response status: {:?}",
+                s
+            )))
+        }
+    };
+
+    if text.trim().is_empty() || text.starts_with("Unknown topic") {
+        let _ = msg
+            .channel_id
+            .send_message(&ctx.http, |m| {
+                m.content(format!("Could not find a cheat sheet for '{}'", query))
+            })
+            .await;
+
+        return Ok(());
+    }
+
+    let title = format!("cheat.sh: {}", query);
+    send_paginated(
+        ctx,
+        msg.channel_id,
+        msg.author.id,
+        code_pages(&title, &text),
+    )
+    .await
+}
@@ -0,0 +1,100 @@
+use super::cache::TldrCache;
+use super::lookup::get_cache;
+use super::platforms::normalize_language;
+use crate::core::{
+    consts::MAIN_COLOR,
+    settings::{get_settings_store, user_settings},
+};
+use serenity::{
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    model::channel::Message,
+    prelude::Context,
+};
+
+#[command]
+#[description(
+    "Sets the language you want to see tldr pages in by default. Pages without a translation \
+    are shown in English. Shows your current language and the available ones without an argument."
+)]
+#[usage("[language]")]
+#[example("de")]
+#[example("en")]
+pub async fn language(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let cache = get_cache(ctx).await;
+
+    let language = match args.single::<String>() {
+        Ok(language_arg) => {
+            let language = find_language(&cache, &language_arg).await?;
+
+            let store = get_settings_store(ctx).await;
+            let mut store = store.write().await;
+            store.update(|settings| {
+                settings.user_mut(msg.author.id).tldr_language = language.clone()
+            })?;
+
+            language
+        }
+        Err(_) => user_settings(ctx, msg.author.id).await.tldr_language,
+    };
+
+    let available = cache.languages().await;
+
+    let _ = msg
+        .channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.colour(MAIN_COLOR)
+                    .title("tl:dr language")
+                    .description(format!(
+                        "You're seeing tldr pages in `{}`.",
+                        language.as_deref().unwrap_or("en")
+                    ));
+
+                if !available.is_empty() {
+                    e.field(
+                        "Available languages",
+                        format!("en, {}", available.join(", ")),
+                        false,
+                    );
+                }
+
+                e
+            })
+        })
+        .await;
+
+    Ok(())
+}
+
+/// Finds the tldr language code for the given language, which is None for English.
+/// Until the pages have been downloaded, any code is accepted.
+pub async fn find_language(
+    cache: &TldrCache,
+    language_arg: &str,
+) -> Result<Option<String>, CommandError> {
+    let language = normalize_language(language_arg);
+
+    if language == "en" {
+        return Ok(None);
+    }
+
+    if !cache.is_synced() {
+        return Ok(Some(language));
+    }
+
+    let languages = cache.languages().await;
+
+    // Also accept the language without its region, like "pt" for "pt_BR"
+    match languages.iter().find(|l| **l == language).or_else(|| {
+        languages
+            .iter()
+            .find(|l| l.split('_').next() == Some(language.as_str()))
+    }) {
+        Some(language) => Ok(Some(language.clone())),
+        None => Err(CommandError::from(format!(
+            "There are no tldr pages in '{}'. Available are: en, {}",
+            language_arg,
+            languages.join(", ")
+        ))),
+    }
+}
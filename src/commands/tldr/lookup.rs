@@ -1,70 +1,174 @@
-use crate::core::consts::MAIN_COLOR;
 use serenity::{
     framework::standard::{macros::command, Args, CommandError, CommandResult},
     model::channel::Message,
     prelude::Context,
 };
 
-use reqwest::{get, StatusCode};
+use super::cache::{TldrCache, TldrCacheContainer};
+use super::language::find_language;
+use super::page::TldrPage;
+use super::platforms::{find_platform, page_paths, DEFAULT_PLATFORM};
+use crate::core::{
+    config::get_config,
+    http::{get_http_client, HttpClient, Service},
+    settings::user_settings,
+    sink::{ChannelSink, MessageSink, OutgoingMessage},
+};
+use reqwest::StatusCode;
+use std::sync::Arc;
 
 #[command]
+#[min_args(1)]
+#[description(
+    "Shows the tldr page of a command. Commands of several words, like `git commit`, \
+    can be written with spaces. Suggests similar pages if there's none with the exact name.\n\
+    The platform defaults to linux, and can be any of common, linux, osx (or macos), windows, android and sunos. \
+    Pages are shown in your default language (see the `language` subcommand), or the one given with `-l <language>`, \
+    falling back to English where there's no translation."
+)]
+#[usage("[platform] [-l <language>] <command>")]
+#[example("git commit")]
+#[example("osx -l de brew")]
 pub async fn lookup(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let platform_arg;
-
-    // If the platform was given as the first argument, try to parse it, otherwise go back to linux
-    let platform = match args.len() {
-        1 => "linux",
-        2 => {
-            platform_arg = args.single::<String>().unwrap();
-
-            if let "linux" | "windows" | "macos" = platform_arg.as_str() {
-                platform_arg.as_str()
-            } else {
-                "linux"
-            }
+    let cache = get_cache(ctx).await;
+
+    let mut platform = DEFAULT_PLATFORM;
+    let mut language = user_settings(ctx, msg.author.id).await.tldr_language;
+
+    // The platform and language options can come in any order, but before the command
+    while let Some(arg) = args.current() {
+        if arg == "-l" || arg == "--language" {
+            let language_arg = args.advance().single::<String>()?;
+            language = find_language(&cache, &language_arg).await?;
+        } else if let Some(found) = find_platform(arg).filter(|_| args.remaining() > 1) {
+            platform = found;
+            args.advance();
+        } else {
+            break;
         }
-        _ => return Err(CommandError::from("Invalid number of arguments")),
+    }
+
+    // tldr names pages of multi-word commands like "git commit" as "git-commit"
+    let search_string = args
+        .iter::<String>()
+        .filter_map(Result::ok)
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase();
+
+    if search_string.is_empty() {
+        return Err(CommandError::from(
+            "You need to name the command to look up",
+        ));
+    }
+
+    let client = get_http_client(ctx).await;
+    let sink = ChannelSink::new(&ctx.http, msg.channel_id);
+
+    send_page(
+        &sink,
+        get_config(ctx).await.prefix(),
+        &cache,
+        &client,
+        platform,
+        language.as_deref(),
+        &search_string,
+    )
+    .await
+}
+
+/// Sends the page, or hints at other platforms and similar pages if there's none
+async fn send_page(
+    sink: &dyn MessageSink,
+    prefix: &str,
+    cache: &TldrCache,
+    client: &HttpClient,
+    platform: &str,
+    language: Option<&str>,
+    search_string: &str,
+) -> CommandResult {
+    // Until the archive has been downloaded for the first time, ask GitHub directly
+    let page = match cache.is_synced() {
+        true => cache.page(platform, language, search_string).await,
+        false => fetch_page(client, platform, language, search_string).await?,
     };
 
-    // Retrieve the command search string argument
-    let search_string = args.single::<String>().unwrap();
+    let platforms = cache.platforms(search_string).await;
+
+    if let Some(page) = page {
+        let mut embed = match TldrPage::parse(&page) {
+            Some(page) => page.embed(),
+            None => return Err(CommandError::from("Couldn't parse tldr markdown")),
+        };
 
-    let tldr_urls = [
-        format!(
-            "https://raw.githubusercontent.com/tldr-pages/tldr/master/pages/{}/{}.md",
-            platform, search_string
+        if !platforms.is_empty() {
+            embed.footer(|f| f.text(format!("Available for: {}", platforms.join(", "))));
+        }
+
+        let _ = sink.send(OutgoingMessage::embed(embed)).await;
+
+        return Ok(());
+    }
+
+    // The page might only exist for other platforms
+    if !platforms.is_empty() {
+        let _ = sink
+            .send(OutgoingMessage::text(format!(
+                "There's no {} page for '{}', but there is one for {}. Try `{}tldr {} {}`.",
+                platform,
+                search_string,
+                platforms.join(", "),
+                prefix,
+                platforms[0],
+                search_string
+            )))
+            .await;
+
+        return Ok(());
+    }
+
+    let suggestions = cache.suggest(search_string).await;
+
+    // Send a message if nothing was found until now
+    let content = match suggestions.is_empty() {
+        true => format!(
+            "Could not find a tl:dr page for '{}'. Try `{}tldr search {}` to find related pages.",
+            search_string, prefix, search_string
         ),
-        format!(
-            "https://raw.githubusercontent.com/tldr-pages/tldr/master/pages/common/{}.md",
-            search_string
+        false => format!(
+            "Could not find a tl:dr page for '{}'. Did you mean {}?",
+            search_string,
+            suggestions
+                .iter()
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(", ")
         ),
-    ];
+    };
+    let _ = sink.send(OutgoingMessage::text(content)).await;
 
-    // Try to find the search string on any url
-    for url in tldr_urls.iter() {
-        let resp = get(url).await.unwrap();
+    Ok(())
+}
+
+/// Fetches a page from the tldr repository, in the same order the cache looks for it
+async fn fetch_page(
+    client: &HttpClient,
+    platform: &str,
+    language: Option<&str>,
+    name: &str,
+) -> Result<Option<String>, CommandError> {
+    // Try to find the page on any url
+    for path in page_paths(platform, language, name) {
+        let url = format!(
+            "https://raw.githubusercontent.com/tldr-pages/tldr/master/{}",
+            path
+        );
+        let resp = client.send(Service::TldrPages, client.get(&url)).await?;
 
         match resp.status() {
             // If the file is not found on the current url, try the next one
             StatusCode::NOT_FOUND => (),
-            // If it was found, parse it, send the embed and Ok() out
-            StatusCode::OK => {
-                let resp_body = &resp.text().await.unwrap();
-
-                let (title, description) = match get_tldr_content_from_markdown(resp_body) {
-                    Some(tuple) => tuple,
-                    None => return Err(CommandError::from("Couldn't parse tldr markdown")),
-                };
-
-                let _ = msg
-                    .channel_id
-                    .send_message(&ctx.http, |m| {
-                        m.embed(|e| e.colour(MAIN_COLOR).title(title).description(description))
-                    })
-                    .await;
-
-                return Ok(());
-            }
+            StatusCode::OK => return Ok(Some(resp.text().await?)),
             // On any other response, throw an error
             s => {
                 return Err(CommandError::from(format!(
@@ -75,29 +179,104 @@ pub async fn lookup(ctx: &Context, msg: &Message, mut args: Args) -> CommandResu
         }
     }
 
-    // Send a message if nothing was found until now
-    let _ = msg
-        .channel_id
-        .send_message(&ctx.http, |m| {
-            m.content(format!(
-                "Could not find a tl:dr page for '{}'",
-                search_string
-            ))
-        })
-        .await;
+    Ok(None)
+}
 
-    Ok(())
+pub(super) async fn get_cache(ctx: &Context) -> Arc<TldrCache> {
+    let data = ctx.data.read().await;
+
+    Arc::clone(data.get::<TldrCacheContainer>().unwrap())
 }
 
-fn get_tldr_content_from_markdown(tldr_body: &str) -> Option<(&str, String)> {
-    let body_lines: Vec<&str> = tldr_body.lines().collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::{
+        embed_fields, embed_footer, embed_str, fixture, temp_dir, MockResponse, MockServer,
+        RecordingSink,
+    };
+    use std::io::{Cursor, Write};
+    use zip::{write::FileOptions, ZipWriter};
+
+    const PAGES: &[&str] = &[
+        "pages/common/tar.md",
+        "pages/common/pwd.md",
+        "pages/linux/apt.md",
+    ];
 
-    match body_lines.as_slice() {
-        [first_line, rest @ ..] => {
-            // Remove any hashtags and spaces at the start of the title line
-            let heading = first_line.trim_start_matches(|c| c == ' ' || c == '#');
-            Some((heading, rest.join("\n")))
+    // An archive like the one published by tldr-pages, made of the vendored pages
+    fn archive() -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for path in PAGES {
+            zip.start_file(*path, FileOptions::default()).unwrap();
+            zip.write_all(fixture(&format!("tldr/{}", path)).as_bytes())
+                .unwrap();
         }
-        _ => None,
+
+        zip.finish().unwrap().into_inner()
+    }
+
+    async fn synced_cache(server: &MockServer, name: &str) -> TldrCache {
+        server.mock("GET", "/tldr.zip", MockResponse::new(200, archive()));
+
+        let cache = TldrCache::new(
+            HttpClient::new(),
+            &temp_dir(name),
+            format!("{}/tldr.zip", server.url()),
+        );
+        cache.sync().await.unwrap();
+
+        cache
+    }
+
+    #[tokio::test]
+    async fn sends_the_page_from_the_cache() {
+        let server = MockServer::start();
+        let cache = synced_cache(&server, "tldr-lookup-page").await;
+        let sink = RecordingSink::new();
+
+        send_page(&sink, "~", &cache, &HttpClient::new(), "linux", None, "tar")
+            .await
+            .unwrap();
+
+        let messages = sink.messages();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(embed_str(&messages[0], "title"), Some("tar"));
+        assert_eq!(embed_fields(&messages[0]).len(), 8);
+        assert_eq!(embed_footer(&messages[0]), Some("Available for: common"));
+    }
+
+    #[tokio::test]
+    async fn suggests_similar_pages() {
+        let server = MockServer::start();
+        let cache = synced_cache(&server, "tldr-lookup-suggest").await;
+        let sink = RecordingSink::new();
+
+        send_page(
+            &sink,
+            "~",
+            &cache,
+            &HttpClient::new(),
+            "linux",
+            None,
+            "tarr",
+        )
+        .await
+        .unwrap();
+        send_page(&sink, "!", &cache, &HttpClient::new(), "osx", None, "apt")
+            .await
+            .unwrap();
+
+        let messages = sink.messages();
+        assert_eq!(
+            messages[0].content.as_deref(),
+            Some("Could not find a tl:dr page for 'tarr'. Did you mean `tar`?")
+        );
+        assert_eq!(
+            messages[1].content.as_deref(),
+            Some(
+                "There's no osx page for 'apt', but there is one for linux. Try `!tldr linux apt`."
+            )
+        );
     }
 }
@@ -0,0 +1,188 @@
+use crate::core::{
+    config::get_config,
+    http::{get_http_client, HttpClient, Service},
+    pagination::{page_builders::code_pages, paginator::send_paginated},
+};
+use reqwest::StatusCode;
+use serenity::{
+    builder::CreateEmbed,
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    model::channel::Message,
+    prelude::Context,
+};
+use std::process::Command;
+
+#[command]
+#[min_args(1)]
+#[max_args(2)]
+#[description(
+    "Shows a man page, one section per page. \
+    Use the reactions to move between sections like NAME, SYNOPSIS and DESCRIPTION."
+)]
+#[usage("<page> [section]")]
+#[example("tar")]
+#[example("printf 3")]
+pub async fn man(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let name = args.single::<String>()?;
+    let section = args.single::<String>().ok();
+
+    // Both end up as arguments to man, so they mustn't be mistaken for options
+    let is_valid = |arg: &str| {
+        !arg.starts_with('-')
+            && arg
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "._+-:".contains(c))
+    };
+    if !is_valid(name.as_str()) || !section.as_deref().is_none_or(is_valid) {
+        return Err(CommandError::from("That's not a valid man page name"));
+    }
+
+    let title = match &section {
+        Some(section) => format!("{}({})", name, section),
+        None => name.clone(),
+    };
+
+    let text = match read_local_page(&name, section.as_deref()).await {
+        Some(text) => Some(text),
+        None => {
+            let client = get_http_client(ctx).await;
+            let base_url = &get_config(ctx).await.tldr.man_page_url;
+            fetch_page(&client, base_url, &name, section.as_deref()).await?
+        }
+    };
+
+    let text = match text {
+        Some(text) => text,
+        None => {
+            let _ = msg
+                .channel_id
+                .send_message(&ctx.http, |m| {
+                    m.content(format!("Could not find a man page for '{}'", title))
+                })
+                .await;
+
+            return Ok(());
+        }
+    };
+
+    let pages: Vec<CreateEmbed> = parse_sections(&text)
+        .iter()
+        .flat_map(|(heading, content)| code_pages(&format!("{} — {}", title, heading), content))
+        .collect();
+
+    if pages.is_empty() {
+        return Err(CommandError::from("Couldn't make sense of that man page"));
+    }
+
+    send_paginated(ctx, msg.channel_id, msg.author.id, pages).await
+}
+
+/// Renders a page from the local man database, if man is installed and has the page
+async fn read_local_page(name: &str, section: Option<&str>) -> Option<String> {
+    let mut command = Command::new("man");
+    command
+        .env("MANPAGER", "cat")
+        .env("MANWIDTH", "80")
+        .env_remove("MAN_KEEP_FORMATTING");
+    if let Some(section) = section {
+        command.arg(section);
+    }
+    command.arg(name);
+
+    let output = tokio::task::spawn_blocking(move || command.output())
+        .await
+        .ok()?
+        .ok()?;
+
+    match output.status.success() {
+        true => Some(strip_overstrike(&String::from_utf8_lossy(&output.stdout))),
+        false => None,
+    }
+}
+
+/// Fetches the page rendered as plain text from the configured man page server
+async fn fetch_page(
+    client: &HttpClient,
+    base_url: &str,
+    name: &str,
+    section: Option<&str>,
+) -> Result<Option<String>, CommandError> {
+    let url = match section {
+        Some(section) => format!("{}/{}.{}.txt", base_url, name, section),
+        None => format!("{}/{}.txt", base_url, name),
+    };
+
+    let resp = client.send(Service::Manuals, client.get(&url)).await?;
+
+    match resp.status() {
+        StatusCode::NOT_FOUND => Ok(None),
+        StatusCode::OK => Ok(Some(strip_overstrike(&resp.text().await?))),
+        s => Err(CommandError::from(format!(
+            "Unexpected response status: {:?}",
+            s
+        ))),
+    }
+}
+
+// Terminal formatting is done by overstriking, e.g. "N\x08N" for a bold N
+fn strip_overstrike(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '\u{8}' => {
+                stripped.pop();
+            }
+            c => stripped.push(c),
+        }
+    }
+
+    stripped
+}
+
+/// Splits a rendered man page into its sections. Section headings are the unindented
+/// lines in upper case, like "NAME" or "SEE ALSO". The header and footer lines are skipped.
+fn parse_sections(text: &str) -> Vec<(String, String)> {
+    let mut sections: Vec<(String, Vec<&str>)> = Vec::new();
+
+    for line in text.lines() {
+        let is_heading = !line.starts_with(char::is_whitespace)
+            && line.chars().any(char::is_alphabetic)
+            && line
+                .chars()
+                .all(|c| c.is_uppercase() || c == ' ' || c == '-' || c == '_');
+
+        if is_heading {
+            sections.push((line.trim().to_string(), Vec::new()));
+        } else if let Some((_, lines)) = sections.last_mut() {
+            // The footer is the only other unindented line
+            if line.starts_with(char::is_whitespace) || line.is_empty() {
+                lines.push(line);
+            }
+        }
+    }
+
+    sections
+        .into_iter()
+        .map(|(heading, lines)| (heading, dedent(&lines)))
+        .filter(|(_, content)| !content.is_empty())
+        .collect()
+}
+
+// Removes the indentation all lines have in common, and surrounding empty lines
+fn dedent(lines: &[&str]) -> String {
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_matches('\n')
+        .to_string()
+}
@@ -1,11 +1,29 @@
 use serenity::framework::standard::macros::group;
 
+mod cache;
+mod cheat;
+mod language;
 mod lookup;
+mod man;
+mod page;
+mod platforms;
+mod search;
 
+pub use self::cache::{run_tldr_sync, TldrCache, TldrCacheContainer};
+
+use self::cheat::CHEAT_COMMAND;
+use self::language::LANGUAGE_COMMAND;
 use self::lookup::LOOKUP_COMMAND;
+use self::man::MAN_COMMAND;
+use self::search::SEARCH_COMMAND;
 
 #[group]
 #[prefixes("tldr")]
 #[default_command(lookup)]
-#[commands(lookup)]
+#[commands(lookup, search, language)]
 struct Tldr;
+
+// The more detailed docs are commands of their own, without the tldr prefix
+#[group]
+#[commands(man, cheat)]
+struct Manuals;
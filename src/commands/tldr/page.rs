@@ -0,0 +1,296 @@
+use crate::core::consts::MAIN_COLOR;
+use serenity::builder::CreateEmbed;
+
+// Discord's limits for embeds
+const FIELD_NAME_LIMIT: usize = 256;
+const FIELD_VALUE_LIMIT: usize = 1024;
+const FIELD_COUNT_LIMIT: usize = 25;
+
+/// A page in the tldr format:
+///
+/// ```md
+/// # tar
+///
+/// > Archiving utility.
+/// > More information: <https://www.gnu.org/software/tar>.
+///
+/// - Create an archive from files:
+///
+/// `tar cf {{target.tar}} {{file1 file2 ...}}`
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct TldrPage {
+    pub name: String,
+    pub description: String,
+    pub more_information: Option<String>,
+    pub examples: Vec<Example>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub description: String,
+    pub command: Vec<CommandPart>,
+}
+
+/// Piece of an example command, either typed as is or a placeholder for the user's own value
+#[derive(Debug, PartialEq)]
+pub enum CommandPart {
+    Literal(String),
+    Placeholder(String),
+}
+
+impl TldrPage {
+    /// Parses a page, returning None if it doesn't start with the "# name" heading
+    pub fn parse(markdown: &str) -> Option<TldrPage> {
+        let mut lines = markdown
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty());
+
+        let name = lines.next()?.strip_prefix('#')?.trim().to_string();
+
+        let mut page = TldrPage {
+            name,
+            ..Default::default()
+        };
+        let mut description_lines = Vec::new();
+        let mut example_description = None;
+
+        for line in lines {
+            if let Some(line) = line.strip_prefix('>') {
+                let line = line.trim();
+
+                match line.strip_prefix("More information:") {
+                    Some(link) => page.more_information = parse_link(link),
+                    None => description_lines.push(line),
+                }
+            } else if let Some(line) = line.strip_prefix('-') {
+                example_description = Some(line.trim().trim_end_matches(':').to_string());
+            } else if line.starts_with('`') && line.ends_with('`') && line.len() > 1 {
+                // A command without a description in front of it is kept with an empty one
+                page.examples.push(Example {
+                    description: example_description.take().unwrap_or_default(),
+                    command: parse_command(&line[1..line.len() - 1]),
+                });
+            }
+        }
+
+        page.description = description_lines.join("\n");
+
+        Some(page)
+    }
+
+    pub fn embed(&self) -> CreateEmbed {
+        let mut embed = CreateEmbed::default();
+        embed.colour(MAIN_COLOR).title(&self.name);
+
+        let mut description = self.description.clone();
+        if let Some(link) = &self.more_information {
+            description.push_str(&format!("\n[More information]({})", link));
+        }
+        embed.description(description);
+
+        for example in self.examples.iter().take(FIELD_COUNT_LIMIT) {
+            let name = match example.description.is_empty() {
+                true => "\u{200b}".to_string(),
+                false => truncate(&example.description, FIELD_NAME_LIMIT),
+            };
+
+            embed.field(
+                name,
+                truncate(&render_command(&example.command), FIELD_VALUE_LIMIT),
+                false,
+            );
+        }
+
+        embed
+    }
+}
+
+// The link is written as "<https://...>.", with the angle brackets and the dot being optional
+fn parse_link(text: &str) -> Option<String> {
+    let link = text
+        .trim()
+        .trim_end_matches('.')
+        .trim_start_matches('<')
+        .trim_end_matches('>');
+
+    match link.is_empty() {
+        true => None,
+        false => Some(link.to_string()),
+    }
+}
+
+/// Splits a command into its literal parts and its "{{placeholder}}" parts
+fn parse_command(command: &str) -> Vec<CommandPart> {
+    let mut parts = Vec::new();
+    let mut rest = command;
+
+    while let Some(start) = rest.find("{{") {
+        let end = match rest[start + 2..].find("}}") {
+            Some(end) => start + 2 + end,
+            // An unclosed placeholder is just text
+            None => break,
+        };
+
+        if start > 0 {
+            parts.push(CommandPart::Literal(rest[..start].to_string()));
+        }
+        parts.push(CommandPart::Placeholder(rest[start + 2..end].to_string()));
+
+        rest = &rest[end + 2..];
+    }
+
+    if !rest.is_empty() {
+        parts.push(CommandPart::Literal(rest.to_string()));
+    }
+
+    parts
+}
+
+/// Formats a command as inline code, with the placeholders underlined to tell them apart
+fn render_command(parts: &[CommandPart]) -> String {
+    parts
+        .iter()
+        .map(|part| match part {
+            CommandPart::Literal(text) => inline_code(text),
+            CommandPart::Placeholder(text) => format!("__{}__", inline_code(text)),
+        })
+        .collect()
+}
+
+// Keeps surrounding whitespace outside of the code span, since Discord trims it inside
+fn inline_code(text: &str) -> String {
+    let code = text.trim();
+    if code.is_empty() {
+        return text.to_string();
+    }
+
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let trailing = &text[text.trim_end().len()..];
+
+    // Backticks inside the code need a longer fence
+    match code.contains('`') {
+        true => format!("{}`` {} ``{}", leading, code, trailing),
+        false => format!("{}`{}`{}", leading, code, trailing),
+    }
+}
+
+fn truncate(text: &str, limit: usize) -> String {
+    match text.chars().count() > limit {
+        true => {
+            let mut truncated: String = text.chars().take(limit - 1).collect();
+            truncated.push('…');
+            truncated
+        }
+        false => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::fixture;
+
+    fn parse_fixture(path: &str) -> TldrPage {
+        TldrPage::parse(&fixture(&format!("tldr/pages/{}", path))).unwrap()
+    }
+
+    fn literal(text: &str) -> CommandPart {
+        CommandPart::Literal(text.to_string())
+    }
+
+    fn placeholder(text: &str) -> CommandPart {
+        CommandPart::Placeholder(text.to_string())
+    }
+
+    #[test]
+    fn parses_a_simple_page() {
+        let page = parse_fixture("common/pwd.md");
+
+        assert_eq!(page.name, "pwd");
+        assert_eq!(page.description, "Print name of current/working directory.");
+        assert_eq!(page.examples.len(), 2);
+        assert_eq!(page.examples[0].description, "Print the current directory");
+        assert_eq!(page.examples[0].command, vec![literal("pwd")]);
+        assert_eq!(page.examples[1].command, vec![literal("pwd -P")]);
+    }
+
+    #[test]
+    fn parses_placeholders() {
+        let page = parse_fixture("common/tar.md");
+
+        assert_eq!(page.name, "tar");
+        assert_eq!(
+            page.description,
+            "Archiving utility.\nOften combined with a compression method, such as `gzip` or `bzip2`."
+        );
+        assert_eq!(page.examples.len(), 8);
+        assert_eq!(
+            page.examples[0].description,
+            "[c]reate an archive and write it to a [f]ile"
+        );
+        assert_eq!(
+            page.examples[0].command,
+            vec![
+                literal("tar cf "),
+                placeholder("path/to/target.tar"),
+                literal(" "),
+                placeholder("path/to/file1 path/to/file2 ..."),
+            ]
+        );
+        // Quotes around a placeholder stay literal
+        assert_eq!(
+            page.examples[7].command,
+            vec![
+                literal("tar xf "),
+                placeholder("path/to/source.tar"),
+                literal(" --wildcards \""),
+                placeholder("*.html"),
+                literal("\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_the_more_information_link() {
+        let page = parse_fixture("linux/apt.md");
+
+        assert_eq!(page.name, "apt");
+        assert_eq!(
+            page.more_information.as_deref(),
+            Some("https://manned.org/apt.8")
+        );
+        // Other links in the description are kept as they are
+        assert_eq!(page.description.lines().count(), 3);
+        assert!(page
+            .description
+            .ends_with("see <https://wiki.archlinux.org/title/Pacman/Rosetta>."));
+        assert_eq!(page.examples.len(), 8);
+        assert_eq!(
+            page.examples[1].command,
+            vec![literal("apt search "), placeholder("package")]
+        );
+    }
+
+    #[test]
+    fn renders_the_embed() {
+        let embed = parse_fixture("common/tar.md").embed();
+
+        assert_eq!(embed.0["title"], "tar");
+        assert!(embed.0["description"]
+            .as_str()
+            .unwrap()
+            .ends_with("\n[More information](https://www.gnu.org/software/tar)"));
+        assert_eq!(
+            embed.0["fields"][0]["value"],
+            "`tar cf` __`path/to/target.tar`__ __`path/to/file1 path/to/file2 ...`__"
+        );
+    }
+
+    #[test]
+    fn rejects_pages_without_heading() {
+        assert_eq!(TldrPage::parse("> Archiving utility."), None);
+        assert_eq!(TldrPage::parse(""), None);
+    }
+}
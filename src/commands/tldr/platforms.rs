@@ -0,0 +1,54 @@
+// All platforms tldr has pages for. Pages in "common" apply to every platform.
+pub const PLATFORMS: [&str; 6] = ["common", "linux", "osx", "windows", "android", "sunos"];
+
+// Platform whose pages are shown when none is given
+pub const DEFAULT_PLATFORM: &str = "linux";
+
+/// Finds the tldr name of a platform, also accepting common alternative names like "macos"
+pub fn find_platform(name: &str) -> Option<&'static str> {
+    let name = name.to_lowercase();
+
+    let platform = match name.as_str() {
+        "macos" | "mac" | "darwin" => "osx",
+        "win" => "windows",
+        "solaris" => "sunos",
+        other => other,
+    };
+
+    PLATFORMS.iter().find(|p| **p == platform).copied()
+}
+
+/// Turns a language code like "pt-br" into the form tldr uses for its directories, "pt_BR"
+pub fn normalize_language(code: &str) -> String {
+    match code.split_once(['-', '_']) {
+        Some((language, region)) => {
+            format!("{}_{}", language.to_lowercase(), region.to_uppercase())
+        }
+        None => code.to_lowercase(),
+    }
+}
+
+/// Paths of the files a page could be in relative to the root of the tldr repository,
+/// in the order they should be tried: the platform's pages before the common ones,
+/// and translated pages before the English ones
+pub fn page_paths(platform: &str, language: Option<&str>, name: &str) -> Vec<String> {
+    let mut page_dirs = Vec::new();
+    if let Some(language) = language {
+        page_dirs.push(format!("pages.{}", language));
+    }
+    page_dirs.push("pages".to_string());
+
+    let mut platforms = vec![platform];
+    if platform != "common" {
+        platforms.push("common");
+    }
+
+    page_dirs
+        .iter()
+        .flat_map(|dir| {
+            platforms
+                .iter()
+                .map(move |platform| format!("{}/{}/{}.md", dir, platform, name))
+        })
+        .collect()
+}
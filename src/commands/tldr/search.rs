@@ -0,0 +1,57 @@
+use super::lookup::get_cache;
+use crate::core::pagination::{page_builders::list_pages, paginator::send_paginated};
+use serenity::{
+    framework::standard::{macros::command, Args, CommandError, CommandResult},
+    model::channel::Message,
+    prelude::Context,
+};
+
+// How many pages are listed per embed page
+const RESULTS_PER_PAGE: usize = 15;
+
+#[command]
+#[min_args(1)]
+#[description("Lists the tldr pages whose name or description contains the keyword")]
+#[usage("<keyword>")]
+#[example("compress")]
+pub async fn search(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let keyword = args.rest().trim();
+
+    let cache = get_cache(ctx).await;
+
+    if !cache.is_synced() {
+        return Err(CommandError::from(
+            "The tldr pages haven't been downloaded yet, try again in a few minutes",
+        ));
+    }
+
+    let results = cache.search(keyword).await;
+
+    if results.is_empty() {
+        let _ = msg
+            .channel_id
+            .send_message(&ctx.http, |m| {
+                m.content(format!(
+                    "Could not find any tl:dr pages matching '{}'",
+                    keyword
+                ))
+            })
+            .await;
+
+        return Ok(());
+    }
+
+    let lines: Vec<String> = results
+        .iter()
+        .map(|page| format!("`{}` ({}): {}", page.name, page.platform, page.description))
+        .collect();
+
+    let title = format!("tl:dr pages matching '{}'", keyword);
+    send_paginated(
+        ctx,
+        msg.channel_id,
+        msg.author.id,
+        list_pages(&title, &lines, RESULTS_PER_PAGE),
+    )
+    .await
+}
@@ -23,16 +23,20 @@ async fn nsfw_check(
 #[check]
 #[name = "IsAdmin"]
 async fn is_admin(
-    ctx: &mut Context,
+    ctx: &Context,
     msg: &Message,
     _: &mut Args,
     _: &CommandOptions,
 ) -> Result<(), Reason> {
-    if let Some(member) = msg.member(&mut ctx.cache).await {
-        if let Ok(permissions) = member.permissions(&ctx.cache) {
-            return permissions.administrator().into();
+    if let Ok(member) = msg.member(ctx).await {
+        if let Ok(permissions) = member.permissions(ctx).await {
+            if permissions.administrator() {
+                return Ok(());
+            }
         }
     }
 
-    false.into()
+    Err(Reason::User(
+        "This command can only be used by server administrators!".to_string(),
+    ))
 }
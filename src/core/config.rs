@@ -0,0 +1,452 @@
+use serde::Deserialize;
+use serenity::prelude::*;
+use std::{env, fmt, fs, io, path::PathBuf, str::FromStr, sync::Arc};
+
+// Read when CONFIG_FILE doesn't name another file. Without a config file, everything comes from the environment.
+const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+const TRANSLATORS: &[&str] = &["deepl", "libretranslate", "google"];
+const WEATHER_PROVIDERS: &[&str] = &["openweathermap", "open-meteo", "mock"];
+
+// The commands of the nsfw group, which isn't registered at all with the feature turned off
+const NSFW_COMMANDS: &[&str] = &[
+    "nhentai",
+    "nhentai search",
+    "nhentai read",
+    "nhentai blacklist",
+    "nhentai autodetect",
+    "booru",
+    "booru random",
+];
+
+pub struct ConfigContainer;
+impl TypeMapKey for ConfigContainer {
+    type Value = Arc<Config>;
+}
+
+/// The bot's configuration, read from a TOML file at startup (see `config.example.toml`).
+/// Every setting can be overridden by an environment variable, listed in `.env.example`.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub token: String,
+    pub prefixes: Vec<String>,
+    // Users allowed to use owner only commands, in addition to the owners of the bot's application
+    pub owners: Vec<u64>,
+    // Directory all persistent data is kept in
+    pub data_dir: PathBuf,
+    // Commands that can't be used, by name
+    pub disabled_commands: Vec<String>,
+    pub features: FeatureConfig,
+    pub weather: WeatherConfig,
+    pub translate: TranslateConfig,
+    pub tldr: TldrConfig,
+    pub sauce: SauceConfig,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureConfig {
+    // The nsfw commands and the lookup of linked galleries
+    pub nsfw: bool,
+    // Keeping a local copy of the tldr pages. Without it, every lookup asks GitHub.
+    pub tldr_sync: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WeatherConfig {
+    // "openweathermap", "open-meteo" or "mock". Without one, OpenWeatherMap is used if there's an api key.
+    pub provider: Option<String>,
+    pub open_weather_map_token: Option<String>,
+    // How long forecasts are reused, in seconds
+    pub cache_ttl: u64,
+    // Tile server the location maps are made from, with {z}, {x} and {y} standing for the tile
+    pub map_tile_url: String,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TranslateConfig {
+    // Backends to use, in order of preference
+    pub translators: Vec<String>,
+    pub deepl_api_key: Option<String>,
+    pub libretranslate_url: Option<String>,
+    pub libretranslate_api_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TldrConfig {
+    pub archive_url: String,
+    // How often the archive is downloaded again, in hours
+    pub sync_interval: u64,
+    pub man_page_url: String,
+    pub cheat_sh_url: String,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SauceConfig {
+    // SauceNAO's search endpoint
+    pub url: String,
+    pub api_key: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            token: String::new(),
+            prefixes: vec!["~".to_string()],
+            owners: Vec::new(),
+            data_dir: PathBuf::from("data"),
+            disabled_commands: Vec::new(),
+            features: Default::default(),
+            weather: Default::default(),
+            translate: Default::default(),
+            tldr: Default::default(),
+            sauce: Default::default(),
+        }
+    }
+}
+
+impl Default for FeatureConfig {
+    fn default() -> Self {
+        FeatureConfig {
+            nsfw: true,
+            tldr_sync: true,
+        }
+    }
+}
+
+impl Default for WeatherConfig {
+    fn default() -> Self {
+        WeatherConfig {
+            provider: None,
+            open_weather_map_token: None,
+            cache_ttl: 600,
+            map_tile_url: "https://tile.openstreetmap.org/{z}/{x}/{y}.png".to_string(),
+        }
+    }
+}
+
+impl Default for TranslateConfig {
+    fn default() -> Self {
+        TranslateConfig {
+            translators: TRANSLATORS.iter().map(|name| name.to_string()).collect(),
+            deepl_api_key: None,
+            libretranslate_url: None,
+            libretranslate_api_key: None,
+        }
+    }
+}
+
+impl Default for TldrConfig {
+    fn default() -> Self {
+        TldrConfig {
+            archive_url: "https://tldr.sh/assets/tldr.zip".to_string(),
+            sync_interval: 24,
+            man_page_url: "https://man.archlinux.org/man".to_string(),
+            cheat_sh_url: "https://cheat.sh".to_string(),
+        }
+    }
+}
+
+impl Default for SauceConfig {
+    fn default() -> Self {
+        SauceConfig {
+            url: "https://saucenao.com/search.php".to_string(),
+            api_key: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read(io::Error),
+    Parse(toml::de::Error),
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Read(why) => write!(f, "Failed to read the config file: {}", why),
+            ConfigError::Parse(why) => write!(f, "The config file is malformed: {}", why),
+            ConfigError::Invalid(problems) => {
+                write!(f, "The configuration is invalid:")?;
+                for problem in problems {
+                    write!(f, "\n- {}", problem)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Reads the config file, applies the environment overrides and checks the result.
+    /// All problems are reported at once, so they can be fixed in one go.
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = env::var("CONFIG_FILE").unwrap_or_else(|_| DEFAULT_CONFIG_FILE.to_string());
+
+        let mut config: Config = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content).map_err(ConfigError::Parse)?,
+            Err(why) if why.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(why) => return Err(ConfigError::Read(why)),
+        };
+
+        let mut problems = Vec::new();
+        config.apply_env(&mut problems);
+        config.validate(&mut problems);
+
+        match problems.is_empty() {
+            true => Ok(config),
+            false => Err(ConfigError::Invalid(problems)),
+        }
+    }
+
+    fn apply_env(&mut self, problems: &mut Vec<String>) {
+        override_value(&mut self.token, "DISCORD_TOKEN", problems);
+        override_list(&mut self.prefixes, "PREFIXES", problems);
+        override_list(&mut self.owners, "OWNERS", problems);
+        override_value(&mut self.data_dir, "DATA_DIR", problems);
+        override_list(&mut self.disabled_commands, "DISABLED_COMMANDS", problems);
+
+        override_value(&mut self.features.nsfw, "ENABLE_NSFW", problems);
+        override_value(&mut self.features.tldr_sync, "ENABLE_TLDR_SYNC", problems);
+
+        let weather = &mut self.weather;
+        override_option(&mut weather.provider, "WEATHER_PROVIDER");
+        override_option(
+            &mut weather.open_weather_map_token,
+            "OPEN_WEATHER_MAP_TOKEN",
+        );
+        override_value(&mut weather.cache_ttl, "WEATHER_CACHE_TTL", problems);
+        override_value(&mut weather.map_tile_url, "WEATHER_MAP_TILE_URL", problems);
+
+        let translate = &mut self.translate;
+        override_list(&mut translate.translators, "TRANSLATORS", problems);
+        override_option(&mut translate.deepl_api_key, "DEEPL_API_KEY");
+        override_option(&mut translate.libretranslate_url, "LIBRETRANSLATE_URL");
+        override_option(
+            &mut translate.libretranslate_api_key,
+            "LIBRETRANSLATE_API_KEY",
+        );
+
+        let tldr = &mut self.tldr;
+        override_value(&mut tldr.archive_url, "TLDR_ARCHIVE_URL", problems);
+        override_value(&mut tldr.sync_interval, "TLDR_SYNC_INTERVAL", problems);
+        override_value(&mut tldr.man_page_url, "MAN_PAGE_URL", problems);
+        override_value(&mut tldr.cheat_sh_url, "CHEAT_SH_URL", problems);
+
+        override_value(&mut self.sauce.url, "SAUCENAO_URL", problems);
+        override_option(&mut self.sauce.api_key, "SAUCENAO_API_KEY");
+    }
+
+    fn validate(&mut self, problems: &mut Vec<String>) {
+        if self.token.trim().is_empty() {
+            problems.push("There's no Discord token, set `token` or DISCORD_TOKEN".to_string());
+        }
+
+        // Empty prefixes would make every message a command
+        self.prefixes = self
+            .prefixes
+            .iter()
+            .map(|prefix| prefix.trim().to_string())
+            .filter(|prefix| !prefix.is_empty())
+            .collect();
+        if self.prefixes.is_empty() {
+            problems.push("At least one command prefix is needed".to_string());
+        }
+
+        for name in self.translate.translators.iter_mut() {
+            *name = name.trim().to_ascii_lowercase();
+
+            if !TRANSLATORS.contains(&name.as_str()) {
+                problems.push(format!(
+                    "Unknown translator '{}', use one of {}",
+                    name,
+                    TRANSLATORS.join(", ")
+                ));
+            }
+        }
+
+        if let Some(provider) = self.weather.provider.as_mut() {
+            *provider = provider.trim().to_ascii_lowercase();
+
+            if !WEATHER_PROVIDERS.contains(&provider.as_str()) {
+                problems.push(format!(
+                    "Unknown weather provider '{}', use one of {}",
+                    provider,
+                    WEATHER_PROVIDERS.join(", ")
+                ));
+            } else if provider == "openweathermap" && self.weather.open_weather_map_token.is_none()
+            {
+                problems.push(
+                    "OpenWeatherMap is selected as weather provider, but there's no api key"
+                        .to_string(),
+                );
+            }
+        }
+
+        if self.tldr.sync_interval == 0 {
+            problems.push("The tldr sync interval needs to be at least one hour".to_string());
+        }
+
+        let mut urls = vec![
+            ("weather.map_tile_url", &self.weather.map_tile_url),
+            ("tldr.archive_url", &self.tldr.archive_url),
+            ("tldr.man_page_url", &self.tldr.man_page_url),
+            ("tldr.cheat_sh_url", &self.tldr.cheat_sh_url),
+            ("sauce.url", &self.sauce.url),
+        ];
+        if let Some(url) = &self.translate.libretranslate_url {
+            urls.push(("translate.libretranslate_url", url));
+        }
+
+        for (name, url) in urls {
+            if let Err(why) = reqwest::Url::parse(url) {
+                problems.push(format!("`{}` isn't a valid url ({}): {}", name, why, url));
+            }
+        }
+    }
+
+    /// The prefix shown in replies and help texts, the first configured one
+    pub fn prefix(&self) -> &str {
+        self.prefixes
+            .first()
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    /// The commands that can't be used with this configuration, with the reason why
+    pub fn disabled_commands(&self) -> Vec<(String, &'static str)> {
+        let mut disabled: Vec<(String, &'static str)> = self
+            .disabled_commands
+            .iter()
+            .map(|name| (name.clone(), "disabled in the config"))
+            .collect();
+
+        if !self.features.nsfw {
+            disabled.extend(
+                NSFW_COMMANDS
+                    .iter()
+                    .map(|name| (name.to_string(), "the nsfw feature is turned off")),
+            );
+        }
+        if self.sauce.api_key.is_none() {
+            disabled.push(("sauce".to_string(), "there's no SauceNAO api key"));
+        }
+
+        disabled
+    }
+}
+
+fn override_value<T: FromStr>(target: &mut T, var: &str, problems: &mut Vec<String>) {
+    if let Ok(value) = env::var(var) {
+        match value.trim().parse() {
+            Ok(value) => *target = value,
+            Err(_) => problems.push(format!("{} has an invalid value: '{}'", var, value)),
+        }
+    }
+}
+
+// Empty variables unset the option, as .env files can't leave out a value otherwise
+fn override_option(target: &mut Option<String>, var: &str) {
+    if let Ok(value) = env::var(var) {
+        let value = value.trim();
+        *target = Some(value.to_string()).filter(|_| !value.is_empty());
+    }
+}
+
+// Lists are given comma separated
+fn override_list<T: FromStr>(target: &mut Vec<T>, var: &str, problems: &mut Vec<String>) {
+    if let Ok(value) = env::var(var) {
+        let items: Result<Vec<T>, _> = value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::parse)
+            .collect();
+
+        match items {
+            Ok(items) => *target = items,
+            Err(_) => problems.push(format!("{} has an invalid value: '{}'", var, value)),
+        }
+    }
+}
+
+pub async fn get_config(ctx: &Context) -> Arc<Config> {
+    let data = ctx.data.read().await;
+
+    Arc::clone(data.get::<ConfigContainer>().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validated(config: &mut Config) -> Vec<String> {
+        let mut problems = Vec::new();
+        config.validate(&mut problems);
+        problems
+    }
+
+    #[test]
+    fn drops_empty_prefixes() {
+        let mut config = Config {
+            token: "token".to_string(),
+            prefixes: vec![" ".to_string(), " ! ".to_string(), "~".to_string()],
+            ..Default::default()
+        };
+
+        assert!(validated(&mut config).is_empty());
+        assert_eq!(config.prefixes, vec!["!", "~"]);
+        assert_eq!(config.prefix(), "!");
+    }
+
+    #[test]
+    fn needs_a_prefix() {
+        let mut config = Config {
+            token: "token".to_string(),
+            prefixes: vec!["".to_string(), "  ".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            validated(&mut config),
+            vec!["At least one command prefix is needed"]
+        );
+    }
+
+    #[test]
+    fn reports_commands_without_their_configuration() {
+        let mut config = Config {
+            disabled_commands: vec!["yell".to_string()],
+            ..Default::default()
+        };
+        config.features.nsfw = false;
+
+        let disabled: Vec<String> = config
+            .disabled_commands()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+
+        assert_eq!(disabled[0], "yell");
+        for name in NSFW_COMMANDS.iter().chain(&["sauce"]) {
+            assert!(
+                disabled.iter().any(|d| d == name),
+                "{} isn't reported",
+                name
+            );
+        }
+
+        config.features.nsfw = true;
+        config.sauce.api_key = Some("key".to_string());
+        assert_eq!(config.disabled_commands().len(), 1);
+    }
+}
@@ -0,0 +1,80 @@
+/// Levenshtein distance between two strings, i.e. the number of single character
+/// insertions, deletions or substitutions needed to turn one into the other
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b_chars.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current_row = vec![i + 1; b_chars.len() + 1];
+
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution_cost = if a_char == *b_char { 0 } else { 1 };
+
+            current_row[j + 1] = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+        }
+
+        previous_row = current_row;
+    }
+
+    previous_row[b_chars.len()]
+}
+
+/// Maximum distance for a candidate to still count as a typo of the input,
+/// growing with the input's length so short inputs don't match everything
+pub fn max_typo_distance(input: &str) -> usize {
+    (input.chars().count() / 3).max(1)
+}
+
+/// Finds the candidates closest to the given input, best matches first.
+/// Comparison is case insensitive, and only candidates within `max_typo_distance` are returned.
+pub fn suggest<'a, I>(input: &str, candidates: I, limit: usize) -> Vec<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let input = input.to_lowercase();
+    let max_distance = max_typo_distance(&input);
+
+    let mut matches: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .map(|candidate| (levenshtein(&input, &candidate.to_lowercase()), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+
+    matches.sort();
+    matches.dedup();
+
+    matches
+        .into_iter()
+        .take(limit)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_the_edit_distance() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        // Characters, not bytes
+        assert_eq!(levenshtein("grün", "grun"), 1);
+    }
+
+    #[test]
+    fn suggests_the_closest_candidates_first() {
+        let candidates = vec!["tar", "tac", "tarr", "cat", "TaRx"];
+
+        assert_eq!(
+            suggest("tarX", candidates.clone(), 3),
+            vec!["TaRx", "tar", "tarr"]
+        );
+        assert!(suggest("zzz", candidates, 3).is_empty());
+    }
+}
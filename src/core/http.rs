@@ -0,0 +1,214 @@
+use log::debug;
+use reqwest::{
+    header::RETRY_AFTER, Client, IntoUrl, Method, RequestBuilder, Response, Result, StatusCode,
+};
+use serenity::prelude::*;
+use std::time::Duration;
+
+// Sent with every request, so the services we use can tell who we are
+pub const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+// How often a failed request is repeated before giving up
+const MAX_RETRIES: u32 = 2;
+
+// Delay before the first retry, doubling with each further one
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+// Services asking to retry later than this get their error passed on instead of being waited for
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(10);
+
+pub struct HttpClientContainer;
+impl TypeMapKey for HttpClientContainer {
+    type Value = HttpClient;
+}
+
+/// The HTTP client shared by all commands, so connections to the same services get reused.
+/// Cloning it is cheap and keeps using the same connection pool.
+#[derive(Clone)]
+pub struct HttpClient {
+    client: Client,
+}
+
+/// The services the bot talks to, each with its own timeout
+#[derive(Clone, Copy, Debug)]
+pub enum Service {
+    Weather,
+    MapTiles,
+    Translate,
+    TldrArchive,
+    TldrPages,
+    Manuals,
+    Nhentai,
+    Booru,
+    Sauce,
+}
+
+impl Service {
+    fn timeout(self) -> Duration {
+        match self {
+            Service::Weather | Service::MapTiles | Service::TldrPages => Duration::from_secs(10),
+            Service::Translate | Service::Manuals | Service::Nhentai | Service::Booru => {
+                Duration::from_secs(15)
+            }
+            // Searching by image makes the service download the image first
+            Service::Sauce => Duration::from_secs(30),
+            // The archive with all pages is several megabytes
+            Service::TldrArchive => Duration::from_secs(120),
+        }
+    }
+}
+
+impl HttpClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .expect("Failed to create the http client");
+
+        HttpClient { client }
+    }
+
+    pub fn get<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn post<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    /// Sends a request with the service's timeout. GET and HEAD requests failing because
+    /// the service is overloaded or unreachable are retried a few times, with increasing delays.
+    /// Other requests only get one try, as repeating them could do their work twice.
+    pub async fn send(&self, service: Service, request: RequestBuilder) -> Result<Response> {
+        let request = request.timeout(service.timeout());
+
+        for attempt in 0..MAX_RETRIES {
+            // Requests with streamed bodies can't be copied, so they only get one try
+            let attempt_request = match request.try_clone() {
+                Some(attempt_request) if is_idempotent(&attempt_request) => attempt_request,
+                _ => break,
+            };
+
+            let delay = match attempt_request.send().await {
+                Ok(response) if is_retryable(response.status()) => match retry_after(&response) {
+                    Some(delay) if delay > MAX_RETRY_AFTER => return Ok(response),
+                    Some(delay) => delay,
+                    None => INITIAL_BACKOFF * 2u32.pow(attempt),
+                },
+                Err(why) if why.is_timeout() || why.is_connect() => {
+                    INITIAL_BACKOFF * 2u32.pow(attempt)
+                }
+                result => return result,
+            };
+
+            debug!(
+                "Request to {:?} failed, retrying in {}ms",
+                service,
+                delay.as_millis()
+            );
+            tokio::time::sleep(delay).await;
+        }
+
+        request.send().await
+    }
+}
+
+fn is_idempotent(request: &RequestBuilder) -> bool {
+    // Building a copy is the only way to get at the method of a request builder
+    match request.try_clone().map(RequestBuilder::build) {
+        Some(Ok(request)) => matches!(*request.method(), Method::GET | Method::HEAD),
+        _ => false,
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+// Only the delay in seconds is supported, not the HTTP date variant
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+pub async fn get_http_client(ctx: &Context) -> HttpClient {
+    let data = ctx.data.read().await;
+
+    data.get::<HttpClientContainer>().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::testing::{MockResponse, MockServer};
+
+    #[tokio::test]
+    async fn retries_failed_get_requests() {
+        let server = MockServer::start();
+        server.mock("GET", "/flaky", MockResponse::new(503, ""));
+        let client = HttpClient::new();
+
+        let response = client
+            .send(
+                Service::Weather,
+                client.get(format!("{}/flaky", server.url())),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(server.requests().len(), MAX_RETRIES as usize + 1);
+    }
+
+    #[tokio::test]
+    async fn waits_as_long_as_asked_to() {
+        let server = MockServer::start();
+        server.mock(
+            "GET",
+            "/busy",
+            MockResponse::new(429, "").header("Retry-After", "60"),
+        );
+        let client = HttpClient::new();
+
+        let response = client
+            .send(
+                Service::Weather,
+                client.get(format!("{}/busy", server.url())),
+            )
+            .await
+            .unwrap();
+
+        // Longer than worth waiting for, so it's passed on right away
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn sends_post_requests_once() {
+        let server = MockServer::start();
+        server.mock("POST", "/translate", MockResponse::new(503, ""));
+        let client = HttpClient::new();
+
+        let response = client
+            .send(
+                Service::Translate,
+                client
+                    .post(format!("{}/translate", server.url()))
+                    .body("text"),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(server.requests().len(), 1);
+    }
+}
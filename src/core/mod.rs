@@ -1,4 +1,13 @@
 pub mod checks;
+pub mod config;
 pub mod consts;
 pub mod context;
-//pub mod pagination;
+pub mod fuzzy;
+pub mod http;
+pub mod pagination;
+pub mod settings;
+pub mod sink;
+pub mod store;
+#[cfg(test)]
+pub mod testing;
+pub mod text;
@@ -1 +1,84 @@
+use crate::core::consts::MAIN_COLOR;
+use serenity::builder::CreateEmbed;
 
+// Discord's limit for embed descriptions
+const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+
+/// Splits a list into pages of at most `per_page` lines each, all with the same title
+pub fn list_pages(title: &str, lines: &[String], per_page: usize) -> Vec<CreateEmbed> {
+    lines
+        .chunks(per_page.max(1))
+        .map(|lines| {
+            let mut description = lines.join("\n");
+            if description.len() > EMBED_DESCRIPTION_LIMIT {
+                description = description
+                    .chars()
+                    .take(EMBED_DESCRIPTION_LIMIT - 1)
+                    .collect();
+                description.push('…');
+            }
+
+            let mut embed = CreateEmbed::default();
+            embed
+                .colour(MAIN_COLOR)
+                .title(title)
+                .description(description);
+            embed
+        })
+        .collect()
+}
+
+/// Splits a text into pages which show it in a code block, keeping its lines and indentation intact.
+/// Lines too long for a single page are broken up.
+pub fn code_pages(title: &str, text: &str) -> Vec<CreateEmbed> {
+    // Leaves room for the code block's backticks
+    let limit = EMBED_DESCRIPTION_LIMIT - 8;
+
+    let mut chunks = vec![String::new()];
+    for line in text.lines().flat_map(|line| split_line(line, limit)) {
+        let chunk = chunks.last_mut().unwrap();
+
+        if !chunk.is_empty() && chunk.len() + line.len() + 1 > limit {
+            chunks.push(line.to_string());
+        } else {
+            if !chunk.is_empty() {
+                chunk.push('\n');
+            }
+            chunk.push_str(line);
+        }
+    }
+
+    chunks
+        .iter()
+        .filter(|chunk| !chunk.trim().is_empty())
+        .map(|chunk| {
+            // Backticks in the text would end the code block early
+            let chunk = chunk.replace("```", "`\u{200b}``");
+
+            let mut embed = CreateEmbed::default();
+            embed
+                .colour(MAIN_COLOR)
+                .title(title)
+                .description(format!("```\n{}\n```", chunk));
+            embed
+        })
+        .collect()
+}
+
+fn split_line(line: &str, limit: usize) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut rest = line;
+
+    while rest.len() > limit {
+        let mut end = limit;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        parts.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+
+    parts.push(rest);
+    parts
+}
@@ -1,55 +1,412 @@
+use serde_json::Value;
 use serenity::{
     builder::CreateEmbed,
     client::Context,
-    model::{channel::Message, id::MessageId},
+    framework::standard::CommandResult,
+    model::{
+        channel::{Message, Reaction, ReactionType},
+        id::{ChannelId, MessageId, UserId},
+    },
+    prelude::*,
 };
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+// How long a pagination reacts to page changes after it was last used
+const PAGINATION_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+const FIRST_EMOJI: &str = "⏮️";
+const PREVIOUS_EMOJI: &str = "◀️";
+const NEXT_EMOJI: &str = "▶️";
+const LAST_EMOJI: &str = "⏭️";
 
+pub struct PaginatorContainer;
+impl TypeMapKey for PaginatorContainer {
+    type Value = Arc<Mutex<Paginator>>;
+}
+
+/// Keeps track of all paginated messages, so reactions to them can be turned into page changes
+#[derive(Default)]
 pub struct Paginator {
-    paginations: Vec<Pagination>,
+    paginations: HashMap<MessageId, Pagination>,
 }
 
 impl Paginator {
-    pub fn handle_reaction(msg_id: MessageId) {}
+    pub fn add_pagination(&mut self, message_id: MessageId, pagination: Pagination) {
+        self.paginations
+            .retain(|_, pagination| pagination.last_used.elapsed() < PAGINATION_TIMEOUT);
+
+        self.paginations.insert(message_id, pagination);
+    }
 
-    pub fn add_pagination(msg: &Message) {}
+    pub fn remove_pagination(&mut self, message_id: MessageId) {
+        self.paginations.remove(&message_id);
+    }
 
-    pub fn remove_pagination(msg: &Message) {}
+    /// The user's most recently used reader in the channel, if it hasn't timed out yet
+    fn latest_reader(
+        &mut self,
+        channel_id: ChannelId,
+        owner: UserId,
+    ) -> Option<(MessageId, &mut Pagination)> {
+        self.paginations
+            .iter_mut()
+            .filter(|(_, p)| p.is_reader && p.channel_id == channel_id && p.owner == owner)
+            .filter(|(_, p)| p.last_used.elapsed() < PAGINATION_TIMEOUT)
+            .max_by_key(|(_, p)| p.last_used)
+            .map(|(message_id, p)| (*message_id, p))
+    }
 }
 
 pub struct Pagination {
-    message: Message,
-    pages: Vec<String>,
+    channel_id: ChannelId,
+    // Only the user who requested the pages can turn them
+    owner: UserId,
+    pages: Vec<CreateEmbed>,
     current_page: usize,
-    total_page_count: usize,
-    page_builder: fn(String) -> CreateEmbed,
-    fetch_next_pages: fn() -> Vec<String>,
+    last_used: Instant,
+    // Readers can also be paged through by sending a page number
+    is_reader: bool,
 }
 
 impl Pagination {
-    pub fn change_page(&mut self, ctx: Context, which_page: PageChange) {
-        let new_page_num = match which_page {
-            PageChange::First => 1,
-            PageChange::Previous => self.current_page - 1,
+    /// Switches to another page, returning the page to show if it changed
+    pub fn change_page(&mut self, which_page: PageChange) -> Option<CreateEmbed> {
+        let new_page = match which_page {
+            PageChange::First => 0,
+            PageChange::Previous => self.current_page.checked_sub(1)?,
             PageChange::Next => self.current_page + 1,
-            PageChange::Last => self.pages.len(),
+            PageChange::Last => self.pages.len() - 1,
         };
 
-        let next_page_data = match self.pages.get(new_page_num - 1) {
-            Some(data) => data,
-            None => return,
-        };
+        if new_page == self.current_page || new_page >= self.pages.len() {
+            return None;
+        }
 
-        // let _ = self
-        //     .message
-        //     .edit(ctx.http, |m| m.embed(|mut e| e.0 = next_embed.0.clone()));
+        self.current_page = new_page;
+        self.last_used = Instant::now();
 
-        self.current_page = new_page_num;
+        Some(self.current_embed())
+    }
+
+    /// Jumps to the page with the given index, returning the page to show if it exists and changed
+    pub fn jump_to(&mut self, page: usize) -> Option<CreateEmbed> {
+        if page == self.current_page || page >= self.pages.len() {
+            return None;
+        }
+
+        self.current_page = page;
+        self.last_used = Instant::now();
+
+        Some(self.current_embed())
+    }
+
+    fn current_embed(&self) -> CreateEmbed {
+        let mut embed = self.pages[self.current_page].clone();
+
+        if self.pages.len() > 1 {
+            let page_counter = format!("Page {}/{}", self.current_page + 1, self.pages.len());
+
+            // Pages can have a footer of their own, which the counter is added to
+            let footer = embed.0.get("footer").cloned().unwrap_or_default();
+            let text = match footer.get("text").and_then(Value::as_str) {
+                Some(text) if !text.is_empty() => format!("{} | {}", text, page_counter),
+                _ => page_counter,
+            };
+
+            embed.footer(|f| {
+                if let Some(icon_url) = footer.get("icon_url").and_then(Value::as_str) {
+                    f.icon_url(icon_url);
+                }
+                f.text(text)
+            });
+        }
+
+        embed
     }
 }
 
+#[derive(Clone, Copy)]
 pub enum PageChange {
     First,
     Previous,
     Next,
     Last,
 }
+
+impl PageChange {
+    fn from_emoji(emoji: &ReactionType) -> Option<Self> {
+        let name = match emoji {
+            ReactionType::Unicode(name) => name.trim_end_matches('\u{fe0f}'),
+            _ => return None,
+        };
+
+        [
+            (FIRST_EMOJI, PageChange::First),
+            (PREVIOUS_EMOJI, PageChange::Previous),
+            (NEXT_EMOJI, PageChange::Next),
+            (LAST_EMOJI, PageChange::Last),
+        ]
+        .iter()
+        .find(|(emoji, _)| emoji.trim_end_matches('\u{fe0f}') == name)
+        .map(|(_, change)| *change)
+    }
+}
+
+/// Sends the given pages as a single message which can be paged through with reactions.
+/// A single page is sent as a plain embed.
+pub async fn send_paginated(
+    ctx: &Context,
+    channel_id: ChannelId,
+    owner: UserId,
+    pages: Vec<CreateEmbed>,
+) -> CommandResult {
+    send_pagination(ctx, channel_id, owner, pages, 0, false).await
+}
+
+/// Sends pages which are read one after the other, like the pages of a gallery,
+/// starting out on the page with the given index. Besides using the reactions,
+/// the owner can jump to a page by sending its number.
+pub async fn send_reader(
+    ctx: &Context,
+    channel_id: ChannelId,
+    owner: UserId,
+    pages: Vec<CreateEmbed>,
+    start_page: usize,
+) -> CommandResult {
+    send_pagination(ctx, channel_id, owner, pages, start_page, true).await
+}
+
+async fn send_pagination(
+    ctx: &Context,
+    channel_id: ChannelId,
+    owner: UserId,
+    pages: Vec<CreateEmbed>,
+    start_page: usize,
+    is_reader: bool,
+) -> CommandResult {
+    if pages.is_empty() {
+        return Ok(());
+    }
+
+    let pagination = Pagination {
+        channel_id,
+        owner,
+        current_page: start_page.min(pages.len() - 1),
+        pages,
+        last_used: Instant::now(),
+        is_reader,
+    };
+    let first_page = pagination.current_embed();
+    let page_count = pagination.pages.len();
+
+    let message = channel_id
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                *e = first_page;
+                e
+            })
+        })
+        .await?;
+
+    if page_count == 1 {
+        return Ok(());
+    }
+
+    get_paginator(ctx)
+        .await
+        .lock()
+        .await
+        .add_pagination(message.id, pagination);
+
+    for emoji in &[FIRST_EMOJI, PREVIOUS_EMOJI, NEXT_EMOJI, LAST_EMOJI] {
+        message
+            .react(ctx, ReactionType::Unicode(emoji.to_string()))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Turns the page of a paginated message when its owner reacts to it with one of the arrows.
+/// Gets called for both added and removed reactions, so each click turns the page.
+pub async fn handle_pagination_reaction(ctx: &Context, reaction: &Reaction) -> CommandResult {
+    let which_page = match PageChange::from_emoji(&reaction.emoji) {
+        Some(which_page) => which_page,
+        None => return Ok(()),
+    };
+
+    let paginator = get_paginator(ctx).await;
+
+    let (channel_id, page) = {
+        let mut paginator = paginator.lock().await;
+
+        let pagination = match paginator.paginations.get_mut(&reaction.message_id) {
+            Some(pagination) => pagination,
+            None => return Ok(()),
+        };
+
+        if reaction.user_id != Some(pagination.owner) {
+            return Ok(());
+        }
+
+        if pagination.last_used.elapsed() >= PAGINATION_TIMEOUT {
+            paginator.remove_pagination(reaction.message_id);
+            return Ok(());
+        }
+
+        match pagination.change_page(which_page) {
+            Some(page) => (pagination.channel_id, page),
+            None => return Ok(()),
+        }
+    };
+
+    channel_id
+        .edit_message(&ctx.http, reaction.message_id, |m| {
+            m.embed(|e| {
+                *e = page;
+                e
+            })
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Jumps to a page when the owner of a reader in the channel sends just the page number.
+/// Other paginations don't react to numbers, as they're just as likely to be part of
+/// the conversation. The number message is deleted again afterwards, if the bot is allowed to.
+pub async fn handle_pagination_jump(ctx: &Context, msg: &Message) -> CommandResult {
+    let page_number = match msg.content.trim().parse::<usize>() {
+        Ok(page_number) if page_number > 0 => page_number,
+        _ => return Ok(()),
+    };
+
+    let paginator = get_paginator(ctx).await;
+
+    let (message_id, page) = {
+        let mut paginator = paginator.lock().await;
+
+        let (message_id, pagination) = match paginator.latest_reader(msg.channel_id, msg.author.id)
+        {
+            Some(found) => found,
+            None => return Ok(()),
+        };
+
+        match pagination.jump_to(page_number - 1) {
+            Some(page) => (message_id, page),
+            None => return Ok(()),
+        }
+    };
+
+    msg.channel_id
+        .edit_message(&ctx.http, message_id, |m| {
+            m.embed(|e| {
+                *e = page;
+                e
+            })
+        })
+        .await?;
+
+    let _ = msg.delete(ctx).await;
+
+    Ok(())
+}
+
+async fn get_paginator(ctx: &Context) -> Arc<Mutex<Paginator>> {
+    let data = ctx.data.read().await;
+
+    Arc::clone(data.get::<PaginatorContainer>().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(description: &str) -> CreateEmbed {
+        let mut embed = CreateEmbed::default();
+        embed.description(description);
+        embed
+    }
+
+    fn pagination(pages: Vec<CreateEmbed>) -> Pagination {
+        Pagination {
+            channel_id: ChannelId(1),
+            owner: UserId(2),
+            pages,
+            current_page: 0,
+            last_used: Instant::now(),
+            is_reader: false,
+        }
+    }
+
+    fn footer(embed: &CreateEmbed) -> &Value {
+        &embed.0["footer"]
+    }
+
+    #[test]
+    fn counts_the_pages_in_the_footer() {
+        let mut pagination = pagination(vec![page("one"), page("two"), page("three")]);
+
+        assert_eq!(footer(&pagination.current_embed())["text"], "Page 1/3");
+
+        let embed = pagination.change_page(PageChange::Last).unwrap();
+        assert_eq!(embed.0["description"], "three");
+        assert_eq!(footer(&embed)["text"], "Page 3/3");
+    }
+
+    #[test]
+    fn keeps_the_footer_of_the_page() {
+        let mut with_footer = page("one");
+        with_footer.footer(|f| {
+            f.text("Tags: cats")
+                .icon_url("https://example.com/icon.png")
+        });
+        let pagination = pagination(vec![with_footer, page("two")]);
+
+        let embed = pagination.current_embed();
+
+        assert_eq!(footer(&embed)["text"], "Tags: cats | Page 1/2");
+        assert_eq!(footer(&embed)["icon_url"], "https://example.com/icon.png");
+    }
+
+    #[test]
+    fn leaves_single_pages_alone() {
+        let pagination = pagination(vec![page("one")]);
+
+        assert!(!pagination.current_embed().0.contains_key("footer"));
+    }
+
+    #[test]
+    fn stays_within_the_pages() {
+        let mut pagination = pagination(vec![page("one"), page("two")]);
+
+        assert!(pagination.change_page(PageChange::Previous).is_none());
+        assert!(pagination.jump_to(5).is_none());
+        assert!(pagination.jump_to(1).is_some());
+        assert!(pagination.change_page(PageChange::Next).is_none());
+    }
+
+    #[test]
+    fn jumps_only_within_readers() {
+        let mut paginator = Paginator::default();
+
+        let mut reader = pagination(vec![page("one"), page("two")]);
+        reader.is_reader = true;
+        paginator.add_pagination(MessageId(10), reader);
+        // A search result list used after the reader
+        paginator.add_pagination(MessageId(11), pagination(vec![page("one"), page("two")]));
+
+        let (message_id, _) = paginator.latest_reader(ChannelId(1), UserId(2)).unwrap();
+        assert_eq!(message_id, MessageId(10));
+
+        assert!(paginator.latest_reader(ChannelId(1), UserId(3)).is_none());
+        assert!(paginator.latest_reader(ChannelId(4), UserId(2)).is_none());
+
+        paginator.remove_pagination(MessageId(10));
+        assert!(paginator.latest_reader(ChannelId(1), UserId(2)).is_none());
+    }
+}
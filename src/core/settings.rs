@@ -0,0 +1,84 @@
+use crate::core::store::JsonStore;
+use serde::{Deserialize, Serialize};
+use serenity::{
+    model::id::{GuildId, UserId},
+    prelude::*,
+};
+use std::{collections::HashMap, sync::Arc};
+
+pub struct GuildSettingsContainer;
+impl TypeMapKey for GuildSettingsContainer {
+    type Value = Arc<RwLock<JsonStore<Settings>>>;
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Settings {
+    // Keyed by guild id. Guilds which never changed a setting aren't stored.
+    #[serde(default)]
+    guilds: HashMap<u64, GuildSettings>,
+    // Keyed by user id, same as the guilds
+    #[serde(default)]
+    users: HashMap<u64, UserSettings>,
+}
+
+/// Per-guild configuration, changeable by the guild's admins
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+#[serde(default)]
+pub struct GuildSettings {
+    // Whether reacting with a flag emoji translates the message
+    pub flag_translations: bool,
+    // nhentai tags whose galleries get their cover hidden, like "tag:gore" or just "gore"
+    pub blacklisted_tags: Vec<String>,
+    // Whether nhentai gallery links posted in nsfw channels get an info card as reply
+    pub nhentai_links: bool,
+    // Whether bare six-digit codes count as gallery links too
+    pub nhentai_codes: bool,
+}
+
+/// Per-user preferences, changeable by the user themselves
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+#[serde(default)]
+pub struct UserSettings {
+    // Language of the tldr pages shown to the user, English if not set
+    pub tldr_language: Option<String>,
+}
+
+impl Settings {
+    pub fn guild(&self, guild_id: GuildId) -> GuildSettings {
+        self.guilds.get(&guild_id.0).cloned().unwrap_or_default()
+    }
+
+    pub fn guild_mut(&mut self, guild_id: GuildId) -> &mut GuildSettings {
+        self.guilds.entry(guild_id.0).or_default()
+    }
+
+    pub fn user(&self, user_id: UserId) -> UserSettings {
+        self.users.get(&user_id.0).cloned().unwrap_or_default()
+    }
+
+    pub fn user_mut(&mut self, user_id: UserId) -> &mut UserSettings {
+        self.users.entry(user_id.0).or_default()
+    }
+}
+
+pub async fn get_settings_store(ctx: &Context) -> Arc<RwLock<JsonStore<Settings>>> {
+    let data = ctx.data.read().await;
+
+    Arc::clone(data.get::<GuildSettingsContainer>().unwrap())
+}
+
+/// Gets the settings of the given guild
+pub async fn guild_settings(ctx: &Context, guild_id: GuildId) -> GuildSettings {
+    let store = get_settings_store(ctx).await;
+    let settings = store.read().await;
+
+    settings.get().guild(guild_id)
+}
+
+/// Gets the settings of the given user
+pub async fn user_settings(ctx: &Context, user_id: UserId) -> UserSettings {
+    let store = get_settings_store(ctx).await;
+    let settings = store.read().await;
+
+    settings.get().user(user_id)
+}
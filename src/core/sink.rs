@@ -0,0 +1,90 @@
+use serenity::{
+    async_trait,
+    builder::CreateEmbed,
+    http::{AttachmentType, Http},
+    model::id::ChannelId,
+    Result,
+};
+use std::borrow::Cow;
+
+/// A message to be sent, independent of where it ends up
+#[derive(Clone, Default)]
+pub struct OutgoingMessage {
+    pub content: Option<String>,
+    pub embed: Option<CreateEmbed>,
+    // Attached files by their file name, embeds can show them with `attachment://<file name>`
+    pub files: Vec<(String, Vec<u8>)>,
+}
+
+impl OutgoingMessage {
+    pub fn text<S: Into<String>>(content: S) -> Self {
+        OutgoingMessage {
+            content: Some(content.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn embed(embed: CreateEmbed) -> Self {
+        OutgoingMessage {
+            embed: Some(embed),
+            ..Default::default()
+        }
+    }
+
+    pub fn file(mut self, filename: &str, data: Vec<u8>) -> Self {
+        self.files.push((filename.to_string(), data));
+        self
+    }
+}
+
+/// Where commands send their replies to. Commands taking a sink instead of the context
+/// can be run without a connection to Discord, with a sink that just records the messages.
+///
+/// Only convert, weather, translate and the tldr lookup send through a sink so far.
+/// The booru, nhentai, sauce, man and cheat commands and the translation bridges
+/// still send to the channel directly, and can't be tested this way yet.
+#[async_trait]
+pub trait MessageSink: Send + Sync {
+    async fn send(&self, message: OutgoingMessage) -> Result<()>;
+}
+
+/// Sends messages to a Discord channel
+pub struct ChannelSink<'a> {
+    http: &'a Http,
+    channel_id: ChannelId,
+}
+
+impl<'a> ChannelSink<'a> {
+    pub fn new(http: &'a Http, channel_id: ChannelId) -> Self {
+        ChannelSink { http, channel_id }
+    }
+}
+
+#[async_trait]
+impl MessageSink for ChannelSink<'_> {
+    async fn send(&self, message: OutgoingMessage) -> Result<()> {
+        self.channel_id
+            .send_message(self.http, |m| {
+                if let Some(content) = message.content {
+                    m.content(content);
+                }
+                if let Some(embed) = message.embed {
+                    m.embed(|e| {
+                        *e = embed;
+                        e
+                    });
+                }
+                for (filename, data) in message.files {
+                    m.add_file(AttachmentType::Bytes {
+                        data: Cow::from(data),
+                        filename,
+                    });
+                }
+
+                m
+            })
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Data which is persisted as a json file inside the configured data directory,
+/// so that it survives restarts. Every update is written back to disk immediately.
+pub struct JsonStore<T> {
+    path: PathBuf,
+    data: T,
+}
+
+impl<T> JsonStore<T>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    /// Loads the store with the given name, or creates an empty one if it doesn't exist yet
+    pub fn open(data_dir: &Path, name: &str) -> io::Result<Self> {
+        let path = data_dir.join(format!("{}.json", name));
+
+        let data = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)?,
+            Err(why) if why.kind() == io::ErrorKind::NotFound => T::default(),
+            Err(why) => return Err(why),
+        };
+
+        Ok(JsonStore { path, data })
+    }
+
+    pub fn get(&self) -> &T {
+        &self.data
+    }
+
+    /// Modifies the stored data and writes it back to disk
+    pub fn update<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> io::Result<R> {
+        let result = f(&mut self.data);
+        self.save()?;
+
+        Ok(result)
+    }
+
+    fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Write to a temporary file first, so a crash mid-write can't leave a corrupted store behind
+        let tmp_path = self.path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(&self.data)?)?;
+        fs::rename(&tmp_path, &self.path)
+    }
+}
@@ -0,0 +1,259 @@
+//! Helpers for running commands in tests, without Discord or the services they talk to
+
+use super::sink::{MessageSink, OutgoingMessage};
+use serde_json::Value;
+use serenity::{async_trait, Result};
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+/// Reads a file from `tests/fixtures`
+pub fn fixture(path: &str) -> String {
+    String::from_utf8(fixture_bytes(path)).expect("Fixture isn't valid UTF-8")
+}
+
+pub fn fixture_bytes(path: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(path);
+
+    fs::read(&path).unwrap_or_else(|why| panic!("Can't read fixture {:?}: {}", path, why))
+}
+
+/// An empty directory of its own for each test, for data the command under test persists
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "{}-test-{}-{}",
+        env!("CARGO_PKG_NAME"),
+        name,
+        std::process::id()
+    ));
+
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("Failed to create the test directory");
+
+    dir
+}
+
+/// Keeps the messages sent to it, instead of sending them anywhere
+#[derive(Default)]
+pub struct RecordingSink {
+    messages: Mutex<Vec<OutgoingMessage>>,
+}
+
+impl RecordingSink {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The messages sent so far, in order
+    pub fn messages(&self) -> Vec<OutgoingMessage> {
+        self.messages.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl MessageSink for RecordingSink {
+    async fn send(&self, message: OutgoingMessage) -> Result<()> {
+        self.messages.lock().unwrap().push(message);
+
+        Ok(())
+    }
+}
+
+/// Reads a value of a recorded embed, e.g. `embed_value(&message, "title")`
+pub fn embed_value<'a>(message: &'a OutgoingMessage, key: &str) -> Option<&'a Value> {
+    message.embed.as_ref()?.0.get(key)
+}
+
+/// Text of a top level embed value like the title or description
+pub fn embed_str<'a>(message: &'a OutgoingMessage, key: &str) -> Option<&'a str> {
+    embed_value(message, key)?.as_str()
+}
+
+/// The embed's fields, as (name, value) pairs
+pub fn embed_fields(message: &OutgoingMessage) -> Vec<(String, String)> {
+    let fields = match embed_value(message, "fields").and_then(Value::as_array) {
+        Some(fields) => fields,
+        None => return Vec::new(),
+    };
+
+    fields
+        .iter()
+        .map(|field| {
+            (
+                field["name"].as_str().unwrap_or_default().to_string(),
+                field["value"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect()
+}
+
+pub fn embed_footer(message: &OutgoingMessage) -> Option<&str> {
+    embed_value(message, "footer")?.get("text")?.as_str()
+}
+
+/// A request received by the mock server
+#[derive(Clone, Debug)]
+pub struct RecordedRequest {
+    pub method: String,
+    // Path including the query
+    pub path: String,
+    pub body: String,
+}
+
+/// A canned response, returned for all requests to a path
+#[derive(Clone)]
+pub struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl MockResponse {
+    pub fn new<B: Into<Vec<u8>>>(status: u16, body: B) -> Self {
+        MockResponse {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+struct Route {
+    method: String,
+    path: String,
+    response: MockResponse,
+}
+
+#[derive(Default)]
+struct ServerState {
+    routes: Vec<Route>,
+    requests: Vec<RecordedRequest>,
+}
+
+/// A minimal HTTP server on a local port, standing in for the services commands talk to.
+/// Unknown paths get an empty 404 response.
+pub struct MockServer {
+    url: String,
+    state: Arc<Mutex<ServerState>>,
+}
+
+impl MockServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to start the mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(ServerState::default()));
+
+        let server_state = Arc::clone(&state);
+        thread::spawn(move || {
+            for stream in listener.incoming().filter_map(|stream| stream.ok()) {
+                let state = Arc::clone(&server_state);
+                thread::spawn(move || handle_connection(stream, &state));
+            }
+        });
+
+        MockServer { url, state }
+    }
+
+    /// Base url of the server, without a trailing slash
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Responds to requests with the given method and path. A path ending in `*` matches
+    /// every path starting with the part before it. Later mocks take precedence.
+    pub fn mock(&self, method: &str, path: &str, response: MockResponse) {
+        self.state.lock().unwrap().routes.push(Route {
+            method: method.to_string(),
+            path: path.to_string(),
+            response,
+        });
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+fn handle_connection(stream: TcpStream, state: &Mutex<ServerState>) {
+    let mut reader = BufReader::new(match stream.try_clone() {
+        Ok(stream) => stream,
+        Err(_) => return,
+    });
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    if reader.read_exact(&mut body).is_err() {
+        return;
+    }
+
+    let response = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(RecordedRequest {
+            method: method.clone(),
+            path: path.clone(),
+            body: String::from_utf8_lossy(&body).to_string(),
+        });
+
+        let path_only = path.split('?').next().unwrap_or_default();
+        state
+            .routes
+            .iter()
+            .rev()
+            .find(|route| {
+                route.method == method
+                    && match route.path.strip_suffix('*') {
+                        Some(prefix) => path_only.starts_with(prefix),
+                        None => path_only == route.path,
+                    }
+            })
+            .map(|route| route.response.clone())
+            .unwrap_or_else(|| MockResponse::new(404, ""))
+    };
+
+    let mut head = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+
+    let mut stream = stream;
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(&response.body);
+    let _ = stream.flush();
+}
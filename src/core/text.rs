@@ -0,0 +1,169 @@
+/// Splits a text into chunks of at most `limit` bytes, preferring to break between sentences,
+/// then between words, and only cutting words apart if a single one exceeds the limit.
+/// Whitespace stays attached to the end of the chunk before it, so joining the chunks
+/// gives back the original text.
+pub fn split_text(text: &str, limit: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for sentence in split_sentences(text) {
+        if sentence.len() <= limit {
+            push_piece(&mut chunks, &mut current, sentence, limit);
+            continue;
+        }
+
+        // Too long on its own, so break it up at words instead
+        for word in sentence.split_inclusive(char::is_whitespace) {
+            for piece in split_chars(word, limit) {
+                push_piece(&mut chunks, &mut current, piece, limit);
+            }
+        }
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+fn push_piece(chunks: &mut Vec<String>, current: &mut String, piece: &str, limit: usize) {
+    if !current.is_empty() && current.len() + piece.len() > limit {
+        chunks.push(std::mem::take(current));
+    }
+
+    current.push_str(piece);
+}
+
+/// Splits a text after every sentence, including the whitespace following it
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let is_sentence_end = match c {
+            '\n' | '。' | '！' | '？' => true,
+            // Only count periods followed by a space, so "3.14" or "example.com" stay intact
+            '.' | '!' | '?' => chars.peek().is_none_or(|(_, next)| next.is_whitespace()),
+            _ => false,
+        };
+
+        if !is_sentence_end {
+            continue;
+        }
+
+        let mut end = i + c.len_utf8();
+        while let Some(&(j, next)) = chars.peek() {
+            if !next.is_whitespace() {
+                break;
+            }
+            end = j + next.len_utf8();
+            chars.next();
+        }
+
+        sentences.push(&text[start..end]);
+        start = end;
+    }
+
+    if start < text.len() {
+        sentences.push(&text[start..]);
+    }
+
+    sentences
+}
+
+/// Cuts a text into pieces of at most `limit` bytes, without splitting characters
+fn split_chars(text: &str, limit: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in text.char_indices() {
+        if i + c.len_utf8() - start > limit && i > start {
+            pieces.push(&text[start..i]);
+            start = i;
+        }
+    }
+
+    if start < text.len() {
+        pieces.push(&text[start..]);
+    }
+
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Splits the text, checking the chunks fit and add up to the original
+    fn split(text: &str, limit: usize) -> Vec<String> {
+        let chunks = split_text(text, limit);
+
+        assert_eq!(chunks.concat(), text);
+        for chunk in &chunks {
+            assert!(chunk.len() <= limit, "{:?} is too long", chunk);
+        }
+
+        chunks
+    }
+
+    #[test]
+    fn keeps_short_texts_whole() {
+        assert_eq!(split("Hello there.", 100), vec!["Hello there."]);
+        assert!(split("", 100).is_empty());
+    }
+
+    #[test]
+    fn splits_at_sentence_ends() {
+        assert_eq!(
+            split("First one. Second one!  Third one?\nFourth", 25),
+            vec!["First one. Second one!  ", "Third one?\nFourth"]
+        );
+    }
+
+    #[test]
+    fn keeps_numbers_and_urls_intact() {
+        assert_eq!(
+            split("Pi is 3.14 exactly. See example.com/a.b for more.", 30),
+            vec!["Pi is 3.14 exactly. ", "See example.com/a.b for more."]
+        );
+    }
+
+    #[test]
+    fn splits_at_cjk_sentence_ends() {
+        // Three bytes per character, so no two sentences fit together
+        assert_eq!(
+            split("今日は晴れです。明日は雨です！本当ですか？", 30),
+            vec!["今日は晴れです。", "明日は雨です！", "本当ですか？"]
+        );
+    }
+
+    #[test]
+    fn splits_long_sentences_at_words() {
+        assert_eq!(
+            split("one two three four five six", 10),
+            vec!["one two ", "three ", "four five ", "six"]
+        );
+    }
+
+    #[test]
+    fn cuts_words_longer_than_the_limit() {
+        let word = "x".repeat(25);
+
+        assert_eq!(
+            split(&format!("a {} b", word), 10),
+            vec!["a ", &word[..10], &word[10..20], "xxxxx b"]
+        );
+    }
+
+    #[test]
+    fn cuts_only_between_characters() {
+        // Two bytes each, so only six of the seven bytes fit
+        assert!(split(&"äöü".repeat(10), 7)
+            .iter()
+            .all(|chunk| chunk.chars().count() == 3));
+
+        assert_eq!(split("🦀🦀🦀", 6), vec!["🦀", "🦀", "🦀"]);
+    }
+}
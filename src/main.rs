@@ -1,18 +1,37 @@
 mod commands;
 mod core;
 
+use crate::commands::misc::{
+    create_provider, create_translator, relay_bridged_message, run_subscription_scheduler,
+    translate_flag_reaction, BridgesContainer, FlagTranslationsContainer, TranslatorContainer,
+    WeatherProviderContainer, WeatherSubscriptionsContainer,
+};
+use crate::commands::nsfw::{detect_gallery_links, BooruRegistry, BooruRegistryContainer};
+use crate::commands::tldr::{run_tldr_sync, TldrCache, TldrCacheContainer};
+use crate::core::config::{get_config, Config, ConfigContainer};
 use crate::core::consts::MAIN_COLOR;
 use crate::core::context::*;
+use crate::core::http::{HttpClient, HttpClientContainer};
+use crate::core::pagination::paginator::{
+    handle_pagination_jump, handle_pagination_reaction, PaginatorContainer,
+};
+use crate::core::settings::GuildSettingsContainer;
+use crate::core::store::JsonStore;
 use chrono::Utc;
-use log::info;
+use log::{info, warn};
 use serenity::{
     async_trait,
     framework::standard::{macros::hook, CommandResult, StandardFramework},
     http::Http,
-    model::{channel::Message, event::ResumedEvent, gateway::Ready},
+    model::{
+        channel::{Message, Reaction},
+        event::ResumedEvent,
+        gateway::Ready,
+        id::UserId,
+    },
     prelude::*,
 };
-use std::{collections::HashSet, env, sync::Arc};
+use std::{collections::HashSet, sync::Arc};
 use sysinfo::{System, SystemExt};
 
 struct Handler;
@@ -25,24 +44,71 @@ impl EventHandler for Handler {
         use serenity::model::gateway::Activity;
         use serenity::model::user::OnlineStatus;
 
-        ctx.set_presence(Some(Activity::listening("~help")), OnlineStatus::Online)
+        let help = format!("{}help", get_config(&ctx).await.prefix());
+        ctx.set_presence(Some(Activity::listening(&help)), OnlineStatus::Online)
             .await
     }
 
     async fn resume(&self, _: Context, _: ResumedEvent) {
         info!("Resumed");
     }
+
+    async fn message(&self, ctx: Context, msg: Message) {
+        if let Err(why) = handle_pagination_jump(&ctx, &msg).await {
+            warn!("Failed to jump to page: {:?}", why);
+        }
+
+        if let Err(why) = relay_bridged_message(&ctx, &msg).await {
+            warn!("Failed to relay bridged message: {:?}", why);
+        }
+
+        if get_config(&ctx).await.features.nsfw {
+            if let Err(why) = detect_gallery_links(&ctx, &msg).await {
+                warn!("Failed to look up linked galleries: {:?}", why);
+            }
+        }
+    }
+
+    async fn reaction_add(&self, ctx: Context, reaction: Reaction) {
+        if let Err(why) = handle_pagination_reaction(&ctx, &reaction).await {
+            warn!("Failed to change page: {:?}", why);
+        }
+
+        if let Err(why) = translate_flag_reaction(&ctx, &reaction).await {
+            warn!("Failed to translate flag reaction: {:?}", why);
+        }
+    }
+
+    async fn reaction_remove(&self, ctx: Context, reaction: Reaction) {
+        if let Err(why) = handle_pagination_reaction(&ctx, &reaction).await {
+            warn!("Failed to change page: {:?}", why);
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() {
-    kankyo::load(false).expect("Failed to load .env file");
+    // The .env file is optional, everything can also be set in the config file.
+    // It's loaded before the logger is set up, as it may configure the log level.
+    let dotenv = kankyo::load(false);
     env_logger::init();
 
-    let token = env::var("DISCORD_TOKEN").expect("Expected a token in the environment");
-    let http = Http::new_with_token(&token);
+    match dotenv {
+        Err(why) if why.kind() == std::io::ErrorKind::NotFound => info!("There's no .env file"),
+        Err(why) => warn!("Could not load the .env file: {}", why),
+        Ok(()) => {}
+    }
+
+    let config = Arc::new(Config::load().unwrap_or_else(|why| panic!("{}", why)));
+
+    let disabled_commands = config.disabled_commands();
+    for (name, reason) in &disabled_commands {
+        warn!("The {} command is disabled, as {}", name, reason);
+    }
+
+    let http = Http::new_with_token(&config.token);
 
-    let (owners, bot_id) = match http.get_current_application_info().await {
+    let (mut owners, bot_id) = match http.get_current_application_info().await {
         Ok(info) => {
             let mut owners = HashSet::new();
 
@@ -60,32 +126,80 @@ async fn main() {
         Err(why) => panic!("Could not access application info: {:?}", why),
     };
 
-    let framework = StandardFramework::new()
+    owners.extend(config.owners.iter().map(|&id| UserId(id)));
+
+    let mut framework = StandardFramework::new()
         .configure(|c| {
             c.on_mention(Some(bot_id))
-                .prefixes(vec!["~"])
+                .prefixes(&config.prefixes)
                 .owners(owners)
+                .disabled_commands(
+                    disabled_commands
+                        .into_iter()
+                        .map(|(name, _)| name)
+                        .collect(),
+                )
         })
         .after(after)
         .group(&commands::tldr::TLDR_GROUP)
+        .group(&commands::tldr::MANUALS_GROUP)
         .group(&commands::misc::MISC_GROUP)
         .group(&commands::system::SYSTEM_GROUP)
-        .group(&commands::nsfw::NSFW_GROUP)
         .help(&commands::help::HELP);
 
-    let mut client = Client::builder(&token)
+    if config.features.nsfw {
+        framework = framework.group(&commands::nsfw::NSFW_GROUP);
+    }
+
+    let mut client = Client::builder(&config.token)
         .event_handler(Handler)
         .framework(framework)
         .await
         .expect("Err creating client");
 
+    let http_client = HttpClient::new();
+    let tldr_cache = Arc::new(TldrCache::new(
+        http_client.clone(),
+        &config.data_dir,
+        config.tldr.archive_url.clone(),
+    ));
+
     {
         let mut data = client.data.write().await;
         data.insert::<ShardManagerContainer>(Arc::clone(&client.shard_manager));
         data.insert::<StartTimeContainer>(Utc::now());
         data.insert::<SysInfoContainer>(System::new_all());
+        data.insert::<ConfigContainer>(Arc::clone(&config));
+        data.insert::<HttpClientContainer>(http_client.clone());
+        data.insert::<PaginatorContainer>(Arc::new(Mutex::new(Default::default())));
+        data.insert::<TldrCacheContainer>(Arc::clone(&tldr_cache));
+        data.insert::<GuildSettingsContainer>(Arc::new(RwLock::new(
+            JsonStore::open(&config.data_dir, "guild_settings")
+                .expect("Failed to load guild settings"),
+        )));
+        data.insert::<TranslatorContainer>(create_translator(&http_client, &config.translate));
+        data.insert::<BridgesContainer>(Arc::new(RwLock::new(
+            JsonStore::open(&config.data_dir, "translation_bridges")
+                .expect("Failed to load translation bridges"),
+        )));
+        data.insert::<FlagTranslationsContainer>(Arc::new(Mutex::new(Default::default())));
+        data.insert::<BooruRegistryContainer>(Arc::new(BooruRegistry::new(&http_client)));
+        data.insert::<WeatherProviderContainer>(create_provider(&http_client, &config.weather));
+        data.insert::<WeatherSubscriptionsContainer>(Arc::new(RwLock::new(
+            JsonStore::open(&config.data_dir, "weather_subscriptions")
+                .expect("Failed to load weather subscriptions"),
+        )));
     }
 
+    if config.features.tldr_sync {
+        tokio::spawn(run_tldr_sync(tldr_cache, config.tldr.sync_interval));
+    }
+
+    tokio::spawn(run_subscription_scheduler(
+        Arc::clone(&client.cache_and_http.http),
+        Arc::clone(&client.data),
+    ));
+
     if let Err(why) = client.start().await {
         println!("Client error: {:?}", why);
     }